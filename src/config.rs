@@ -0,0 +1,298 @@
+use core::fmt;
+use core::time::Duration;
+
+/// The prefixes used to scale counts, which also determine their divisor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Prefixes {
+    /// SI prefixes with a `1000` divisor: `k`, `M`, `G`, `T`, `P`, `E`, `Z`, `Y`, `R`, and `Q`.
+    Si,
+    /// SI prefixes with a `1024` divisor, with an uppercase `K`: `K`, `M`, `G`, `T`, `P`, `E`, `Z`, `Y`, `R`, and `Q`.
+    Si1024,
+    /// IEC prefixes with a `1024` divisor: `Ki`, `Mi`, `Gi`, `Ti`, `Pi`, `Ei`, `Zi`, `Yi`, `Ri`, and `Qi`.
+    Iec,
+}
+
+/// The notation used to render bits, in counts from [`human_count_bits`](crate::HumanCount::human_count_bits)
+/// and throughputs from [`human_throughput_bits`](crate::HumanThroughput::human_throughput_bits).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BitNotation {
+    /// The `bit` unit, e.g. `1.2Mbit` and `1.2Mbit/s`.
+    Bit,
+    /// The `b` unit, with `ps` for throughputs per second, e.g. `1.2Mb` and `1.2Mbps`.
+    Bps,
+}
+
+/// When throughputs are rendered as the time per item instead, like `2.3s/it` or `4:10/file`,
+/// through the duration style, where a bare unit becomes `it`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimePerItem {
+    /// Never, always rendering items per time, down to per day, e.g. `1.5files/min`.
+    Never,
+    /// Only when the rate falls below one per second, but is not zero, e.g. `40s/file`.
+    Auto,
+    /// Always, even for fast rates, e.g. `2ms/file`.
+    Always,
+}
+
+/// The precision used to round values before their prefixes and units.
+///
+/// It applies to counts, throughputs, and durations below one minute, as well as to the seconds
+/// of the clock style when given in [`Decimals`](Precision::Decimals), while the other duration
+/// styles always use whole parts. A precision given in the format string, like `{:.2}`, is always
+/// used as [`Decimals`](Precision::Decimals). Either way, no more than 9 decimals are rendered,
+/// so `{:.20}` is the same as `{:.9}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Precision {
+    /// Up to two decimals, increasing with the larger prefixes, without ever generating `.0` or
+    /// `.00`, e.g. `1.2MB` and `1.23GB`.
+    Auto,
+    /// A fixed number of decimals, up to 9, e.g. `1.20MB` with 2.
+    Decimals(u8),
+    /// A number of significant digits, e.g. `1.20GB`, `12.0GB`, and `120GB` with 3.
+    Significant(u8),
+}
+
+impl Precision {
+    const MAX_DECIMALS: i32 = 9;
+
+    /// The decimals to use for a value with the given decimal exponent, where `auto` is the
+    /// default one for its scale.
+    pub(crate) fn decimals(self, auto: usize, exp: i32) -> usize {
+        match self {
+            Precision::Auto => auto,
+            Precision::Decimals(n) => (n as i32).min(Self::MAX_DECIMALS) as usize,
+            Precision::Significant(n) => {
+                (n.max(1) as i32 - 1 - exp).clamp(0, Self::MAX_DECIMALS) as usize
+            }
+        }
+    }
+}
+
+/// The largest unit used to render long durations, which would otherwise grow in hours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TimeUnit {
+    /// Hours, e.g. `277:46:40`.
+    Hours,
+    /// Days, e.g. `11d 13:46:40`.
+    Days,
+    /// Weeks, e.g. `1w 4d 13:46:40`.
+    Weeks,
+    /// Years of 365 days, e.g. `1y 2w 3d 13:46:40`.
+    Years,
+}
+
+/// The style used to render durations.
+///
+/// Durations shorter than one minute are always rendered with a single scale, like `3.44s`,
+/// which is spelled out in the [`Long`](DurationStyle::Long) style, like `3.44 seconds`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DurationStyle {
+    /// Clock style, e.g. `1:04:48` or `11d 13:46:40`.
+    Clock,
+    /// Compound units style, e.g. `1h4m48s`, or `1h 4m 48s` with spaces.
+    Compound,
+    /// Long natural language style, e.g. `1 hour, 4 minutes and 48 seconds`, which becomes
+    /// `about 1 hour` when limited to one part.
+    Long,
+    /// Relative time style, e.g. `3 minutes ago`, `in 2 hours`, `just now`, or `yesterday`,
+    /// where negative values are in the past. It always uses a single part, even below a minute.
+    Relative,
+    /// ISO 8601 style, e.g. `PT1H4M48.395S` or `P11DT13H46M40S`, including below a minute.
+    /// It uses days only when allowed by the max unit, and never weeks or years.
+    Iso8601,
+}
+
+/// Runtime configuration for rendering human representations.
+///
+/// The default configuration follows the enabled Rust features, so `1024`, `iec`, and `space`
+/// keep working as before, but any [`HumanCountData`](crate::HumanCountData),
+/// [`HumanDurationData`](crate::HumanDurationData), or [`HumanThroughputData`](crate::HumanThroughputData)
+/// can be rendered with a different one, regardless of the features.
+#[cfg_attr(
+    not(any(feature = "1024", feature = "iec", feature = "space")),
+    doc = r#"
+
+```
+use human_repr::{HumanConfig, HumanCount, HumanThroughput, Prefixes};
+
+let disk = HumanConfig::new().prefixes(Prefixes::Iec).space(true);
+assert_eq!("1.2 MiB", 1234567.human_count_bytes().with_config(disk));
+assert_eq!("1.2MB/s", 1234567.human_throughput_bytes());
+```
+"#
+)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HumanConfig {
+    pub(crate) prefixes: Prefixes,
+    pub(crate) space: bool,
+    pub(crate) sub_units: bool,
+    pub(crate) fixed_width: bool,
+    pub(crate) bit_notation: BitNotation,
+    pub(crate) time_per_item: TimePerItem,
+    pub(crate) max_unit: TimeUnit,
+    pub(crate) duration_style: DurationStyle,
+    pub(crate) max_parts: u8,
+    pub(crate) precision: Precision,
+    pub(crate) just_now: Duration,
+    pub(crate) yesterday: Duration,
+    pub(crate) nan: &'static str,
+    pub(crate) infinity: &'static str,
+}
+
+impl HumanConfig {
+    /// Create a new configuration, with the defaults set by the enabled Rust features.
+    pub const fn new() -> Self {
+        HumanConfig {
+            prefixes: match (cfg!(feature = "iec"), cfg!(feature = "1024")) {
+                (false, false) => Prefixes::Si,
+                (false, true) => Prefixes::Si1024,
+                (true, _) => Prefixes::Iec,
+            },
+            space: cfg!(feature = "space"),
+            sub_units: false,
+            fixed_width: false,
+            bit_notation: BitNotation::Bit,
+            time_per_item: TimePerItem::Never,
+            max_unit: TimeUnit::Days,
+            duration_style: DurationStyle::Clock,
+            max_parts: u8::MAX,
+            precision: Precision::Auto,
+            just_now: Duration::from_secs(10),
+            yesterday: Duration::from_secs(2 * 86400),
+            nan: "NaN",
+            infinity: "∞",
+        }
+    }
+
+    /// Set the prefixes used to scale counts and throughputs.
+    pub const fn prefixes(mut self, prefixes: Prefixes) -> Self {
+        self.prefixes = prefixes;
+        self
+    }
+
+    /// Set whether to include a space between values and prefixes/units.
+    pub const fn space(mut self, space: bool) -> Self {
+        self.space = space;
+        self
+    }
+
+    /// Set whether counts below one are scaled down with the SI prefixes `m`, `µ`, `n`, and `p`,
+    /// always with a `1000` divisor, e.g. `1.2mV`. It is disabled by default.
+    pub const fn sub_units(mut self, sub_units: bool) -> Self {
+        self.sub_units = sub_units;
+        self
+    }
+
+    /// Set whether to always render the same number of chars for a given config and unit, so live
+    /// updating values don't jitter, e.g. `  9.99MB` and ` 10.00MB`. It is disabled by default.
+    ///
+    /// The values are right-aligned, and use 2 fixed decimals unless a precision is set, except
+    /// in the clock style, which always has one decimal below an hour. It covers all prefixes,
+    /// and durations below one day in the clock style, while negative values take one more char.
+    /// With [`Precision::Significant`], values below a tenth can take more decimals than it has.
+    pub const fn fixed_width(mut self, fixed_width: bool) -> Self {
+        self.fixed_width = fixed_width;
+        self
+    }
+
+    /// Set the notation used to render bits, which defaults to [`BitNotation::Bit`].
+    pub const fn bit_notation(mut self, notation: BitNotation) -> Self {
+        self.bit_notation = notation;
+        self
+    }
+
+    /// Set when throughputs are rendered as the time per item, which defaults to
+    /// [`TimePerItem::Never`].
+    pub const fn time_per_item(mut self, time_per_item: TimePerItem) -> Self {
+        self.time_per_item = time_per_item;
+        self
+    }
+
+    /// Set the largest unit used to render long durations, which defaults to [`TimeUnit::Days`].
+    pub const fn max_unit(mut self, max_unit: TimeUnit) -> Self {
+        self.max_unit = max_unit;
+        self
+    }
+
+    /// Set the style used to render durations, which defaults to [`DurationStyle::Clock`].
+    pub const fn duration_style(mut self, style: DurationStyle) -> Self {
+        self.duration_style = style;
+        self
+    }
+
+    /// Set the maximum number of parts in compound and long durations, rounding the last one.
+    /// By default, all of them are rendered.
+    pub const fn max_parts(mut self, max_parts: u8) -> Self {
+        self.max_parts = max_parts;
+        self
+    }
+
+    /// Set the precision used to round values, which defaults to [`Precision::Auto`].
+    ///
+    /// The seconds of the clock style only honor [`Precision::Decimals`], and keep their single
+    /// trimmed decimal otherwise.
+    pub const fn precision(mut self, precision: Precision) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Set the threshold below which relative times are rendered as `just now`, which defaults to
+    /// 10 seconds.
+    pub const fn just_now(mut self, just_now: Duration) -> Self {
+        self.just_now = just_now;
+        self
+    }
+
+    /// Set the threshold below which relative times of at least one day, once rounded to whole
+    /// days, are rendered as `yesterday` or `tomorrow`, which defaults to 2 days. Use zero to
+    /// disable them.
+    pub const fn yesterday(mut self, yesterday: Duration) -> Self {
+        self.yesterday = yesterday;
+        self
+    }
+
+    /// Set what NaN values are rendered as, without any units, which defaults to `NaN`,
+    /// e.g. `—` for a division by zero in a metrics pipeline.
+    pub const fn nan(mut self, nan: &'static str) -> Self {
+        self.nan = nan;
+        self
+    }
+
+    /// Set what infinite values are rendered as, with their sign and units, which defaults to
+    /// `∞`, e.g. `∞B/s` or `-∞B`. Durations have no units, so they are just `∞` or `-∞`.
+    pub const fn infinity(mut self, infinity: &'static str) -> Self {
+        self.infinity = infinity;
+        self
+    }
+
+    /// Apply the options of a format string, like the precision in `{:.2}`, which is capped at
+    /// 9 decimals when rendered.
+    pub(crate) fn with_formatter(self, f: &fmt::Formatter<'_>) -> Self {
+        match f.precision() {
+            Some(n) => self.precision(Precision::Decimals(n.min(u8::MAX as usize) as u8)),
+            None => self,
+        }
+    }
+
+    /// The precision of values with prefixes or scales, which defaults to 2 fixed decimals in
+    /// the fixed-width mode.
+    pub(crate) fn scaled_precision(&self) -> Precision {
+        match (self.precision, self.fixed_width) {
+            (Precision::Auto, true) => Precision::Decimals(2),
+            (precision, _) => precision,
+        }
+    }
+
+    pub(crate) const fn separator(&self) -> &'static str {
+        match self.space {
+            true => " ",
+            false => "",
+        }
+    }
+}
+
+impl Default for HumanConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
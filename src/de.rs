@@ -0,0 +1,58 @@
+//! The serialized forms of the data types, which skip their exact integers, so deserializing them
+//! rebuilds those from whole values, to render just like before.
+
+use super::{HumanConfig, HumanCountData, HumanDurationData, HumanThroughputData, Unit};
+use crate::human_duration::NANOS;
+use crate::utils::Exact;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct CountRepr<'a> {
+    val: f64,
+    unit: Unit<'a>,
+}
+
+impl<'a> From<CountRepr<'a>> for HumanCountData<'a> {
+    fn from(CountRepr { val, unit }: CountRepr<'a>) -> Self {
+        HumanCountData {
+            val,
+            unit,
+            exact: Exact::from_whole(val),
+            empty_sum: false,
+            config: HumanConfig::new(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct DurationRepr {
+    val: f64,
+}
+
+impl From<DurationRepr> for HumanDurationData {
+    fn from(DurationRepr { val }: DurationRepr) -> Self {
+        HumanDurationData {
+            val,
+            exact: Exact::from_whole(val).and_then(|e| e.checked_mul(NANOS)),
+            config: HumanConfig::new(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ThroughputRepr<'a> {
+    val: f64,
+    unit: Unit<'a>,
+}
+
+impl<'a> From<ThroughputRepr<'a>> for HumanThroughputData<'a> {
+    fn from(ThroughputRepr { val, unit }: ThroughputRepr<'a>) -> Self {
+        HumanThroughputData {
+            val,
+            unit,
+            exact: Exact::from_whole(val),
+            empty_sum: false,
+            config: HumanConfig::new(),
+        }
+    }
+}
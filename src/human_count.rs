@@ -1,39 +1,344 @@
-use super::HumanCountData;
-use crate::utils::{self, SPACE};
-use std::fmt::{self, Debug, Display};
-
-// with default features we get: SI symbols, 1000 divisor, and no spaces.
-const SPEC: &[&str] = {
-    match (cfg!(feature = "iec"), cfg!(feature = "1024")) {
-        (false, false) => &["", "k", "M", "G", "T", "P", "E", "Z", "Y"], // SI (1000).
-        (false, true) => &["", "K", "M", "G", "T", "P", "E", "Z", "Y"],  // SI (1024).
-        (true, _) => &["", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei", "Zi", "Yi"], // IEC (1024)
-    }
-};
-const DECIMALS: &[usize] = &[1, 1, 1, 2, 2, 2, 2, 2, 2];
-const DIVISOR: f64 = {
-    match cfg!(feature = "1024") {
-        true => 1024.,
-        false => 1000.,
-    }
-};
+use super::{BitNotation, HumanConfig, HumanCountData, HumanParseError, HumanStr, Prefixes, BITS};
+use crate::parse;
+#[cfg(not(any(feature = "std", test)))]
+use crate::utils::Float;
+use crate::utils::{self, Exact};
+use crate::Unit;
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Display};
+use core::hash::{Hash, Hasher};
+#[cfg(feature = "alloc")]
+use core::str::FromStr;
+
+const DECIMALS: &[usize] = &[1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2];
+const SUB_SPEC: &[&str] = &["m", "µ", "n", "p"]; // always 1000 divisor, with the non-ASCII "µ".
+const SUB_ALIASES: &[(&str, usize)] = &[("u", 1), ("μ", 1)]; // ASCII u, and greek mu.
+
+// the default config follows the features: SI symbols, 1000 divisor, and no spaces.
+fn spec(prefixes: Prefixes) -> (&'static [&'static str], f64) {
+    match prefixes {
+        Prefixes::Si => (
+            &["", "k", "M", "G", "T", "P", "E", "Z", "Y", "R", "Q"],
+            1000.,
+        ),
+        Prefixes::Si1024 => (
+            &["", "K", "M", "G", "T", "P", "E", "Z", "Y", "R", "Q"],
+            1024.,
+        ),
+        Prefixes::Iec => (
+            &[
+                "", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei", "Zi", "Yi", "Ri", "Qi",
+            ],
+            1024.,
+        ),
+    }
+}
+
+impl<'a> HumanCountData<'a> {
+    /// The maximum length in bytes of any count, besides its unit, as long as the NaN and
+    /// infinity strings are the default ones.
+    pub const MAX_LEN: usize = 19;
+
+    /// Create a count of a raw value in the given unit, without any prefixes applied.
+    #[cfg_attr(
+        not(any(feature = "1024", feature = "iec", feature = "space")),
+        doc = r#"
+
+```
+use human_repr::HumanCountData;
+assert_eq!("1.2MB", HumanCountData::new(1234567., "B"));
+```
+"#
+    )]
+    pub fn new(val: f64, unit: impl Into<Unit<'a>>) -> Self {
+        HumanCountData {
+            val,
+            unit: unit.into(),
+            exact: None,
+            empty_sum: false,
+            config: HumanConfig::new(),
+        }
+    }
+
+    /// Render this count with a custom [`HumanConfig`], instead of the default one.
+    pub fn with_config(mut self, config: HumanConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Render this count inline, without allocating, if it fits in `N` bytes.
+    #[cfg_attr(
+        not(any(feature = "1024", feature = "iec", feature = "space")),
+        doc = r#"
+
+```
+use human_repr::{HumanCount, HumanCountData, HumanStr};
+
+const N: usize = HumanCountData::MAX_LEN + "B".len();
+let s: HumanStr<N> = 1234567.human_count_bytes().render().unwrap();
+assert_eq!("1.2MB", s);
+assert_eq!(5, s.len());
+```
+"#
+    )]
+    pub fn render<const N: usize>(&self) -> Option<HumanStr<N>> {
+        HumanStr::render(self)
+    }
+
+    /// Render this count into a buffer, returning the number of bytes written, which is zero
+    /// when it doesn't fit.
+    pub fn write_to(&self, buf: &mut [u8]) -> usize {
+        utils::write_to(buf, self)
+    }
+
+    /// The raw value of this count, without any prefixes applied.
+    pub fn value(&self) -> f64 {
+        self.val
+    }
+
+    /// The unit of this count.
+    // the unit is already a `&str` without alloc, but a `Cow` to borrow from with it.
+    #[cfg_attr(not(feature = "alloc"), allow(clippy::needless_borrow))]
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    /// The [`HumanConfig`] this count is rendered with.
+    pub fn config(&self) -> HumanConfig {
+        self.config
+    }
+
+    /// Convert this count into one that owns its unit, so it can outlive the borrowed one.
+    #[cfg(feature = "alloc")]
+    pub fn into_owned(self) -> HumanCountData<'static> {
+        HumanCountData {
+            val: self.val,
+            unit: Unit::Owned(self.unit.into_owned()),
+            exact: self.exact,
+            empty_sum: self.empty_sum,
+            config: self.config,
+        }
+    }
+
+    /// Convert this count of bytes into bits, like [`human_count_bits`](crate::HumanCount::human_count_bits)
+    /// would generate.
+    #[cfg_attr(
+        not(any(feature = "1024", feature = "iec", feature = "space")),
+        doc = r#"
+
+```
+use human_repr::HumanCount;
+assert_eq!("9.9Mbit", 1234567.human_count_bytes().into_bits());
+```
+"#
+    )]
+    pub fn into_bits(self) -> HumanCountData<'static> {
+        HumanCountData {
+            val: self.val * 8.,
+            unit: Unit::from(BITS),
+            exact: self.exact.and_then(|e| e.checked_mul(8)),
+            empty_sum: false,
+            config: self.config,
+        }
+    }
+
+    /// Parse a human count like `"43.21GB"` or `"540.5 kPackets"` back into its value and unit.
+    ///
+    /// Both SI and IEC prefixes are understood, as well as the scientific notation used beyond
+    /// them, like `"3.4e38B"`, and the unit is borrowed from the input, except for bits in the
+    /// `bps` notation, like `"1.2Mb"`, which become `bit`.
+    /// Note that units starting with a prefix symbol are ambiguous, in which case the prefix
+    /// always wins, e.g. `"5Mol"` is parsed as 5 million `ol`s.
+    #[cfg_attr(
+        not(any(feature = "1024", feature = "iec", feature = "space")),
+        doc = r#"
+
+```
+use human_repr::{HumanCount, HumanCountData};
+let h = HumanCountData::parse("540.5kPackets")?;
+assert_eq!(540500.human_count("Packets"), h);
+# Ok::<(), human_repr::HumanParseError>(())
+```
+"#
+    )]
+    pub fn parse(s: &'a str) -> Result<Self, HumanParseError> {
+        Self::parse_with(s, HumanConfig::new())
+    }
+
+    /// Parse a human count like [`parse`](Self::parse), but with a custom [`HumanConfig`],
+    /// which determines the SI prefixes divisor and whether sub-unit prefixes are understood,
+    /// and is also set in the result.
+    pub fn parse_with(s: &'a str, config: HumanConfig) -> Result<Self, HumanParseError> {
+        let (num, rest) = parse::split_number(s)?;
+        let own = config.prefixes;
+        let found = [Prefixes::Iec, own, Prefixes::Si, Prefixes::Si1024]
+            .into_iter()
+            .find_map(|prefixes| {
+                let (spec, divisor) = spec(prefixes);
+                (1..spec.len())
+                    .find(|&i| rest.starts_with(spec[i]))
+                    .map(|i| (i, divisor, spec[i].len()))
+            });
+        let sub = SUB_SPEC.iter().copied().zip(0..);
+        let sub = sub
+            .chain(SUB_ALIASES.iter().copied())
+            .find(|&(scale, _)| config.sub_units && rest.starts_with(scale));
+        let (val, unit) = match (found, sub) {
+            (None, Some((scale, i))) => {
+                let val = parse::parse_scaled(num, -3 * (i as i32 + 1));
+                (val, &rest[scale.len()..])
+            }
+            (None, None) => (parse::parse_scaled(num, 0), rest),
+            (Some((i, divisor, len)), _) => {
+                let val = match divisor == 1000. {
+                    true => parse::parse_scaled(num, 3 * i as i32),
+                    false => parse::parse_scaled(num, 0) * divisor.powi(i as i32),
+                };
+                (val, &rest[len..])
+            }
+        };
+        Ok(HumanCountData {
+            val,
+            unit: Unit::from(parsed_unit(unit)),
+            exact: None,
+            empty_sum: false,
+            config,
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromStr for HumanCountData<'static> {
+    type Err = HumanParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let h = HumanCountData::parse(s)?;
+        Ok(HumanCountData {
+            unit: Unit::Owned(h.unit.into_owned()),
+            ..h
+        })
+    }
+}
 
 impl Display for HumanCountData<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let HumanCountData { mut val, unit } = self;
-        for (&scale, &dec) in SPEC.iter().zip(DECIMALS) {
-            match utils::rounded(val, dec) {
-                r if r.abs() >= DIVISOR => val /= DIVISOR,
-                r if r.fract() == 0. => return write!(f, "{:.0}{}{}{}", r, SPACE, scale, unit),
-                r if (r * 10.).fract() == 0. => {
-                    return write!(f, "{:.1}{}{}{}", r, SPACE, scale, unit)
+        let config = self.config.with_formatter(f);
+        let width = fixed_width(self.unit(), &config);
+        utils::pad(f, width, |f| fmt_count(f, self, &config))
+    }
+}
+
+/// The unit as rendered, where bits follow the config notation.
+pub(crate) fn rendered_unit<'u>(unit: &'u str, config: &HumanConfig) -> &'u str {
+    match (unit, config.bit_notation) {
+        (BITS, BitNotation::Bps) => "b",
+        _ => unit,
+    }
+}
+
+/// The unit as parsed, where bits in any notation are normalized.
+pub(crate) fn parsed_unit(unit: &str) -> &str {
+    match unit {
+        "b" => BITS,
+        _ => unit,
+    }
+}
+
+/// The width of any count with this unit in the fixed-width mode, up to the last prefix.
+pub(crate) fn fixed_width(unit: &str, config: &HumanConfig) -> Option<usize> {
+    let (spec, divisor) = spec(config.prefixes);
+    let int = if divisor == 1000. { 3 } else { 4 };
+    let prefix = spec.iter().map(|s| s.chars().count()).max().unwrap_or(0);
+    config.fixed_width.then(|| {
+        utils::num_width(config.scaled_precision(), int, !config.sub_units)
+            + config.separator().len()
+            + prefix
+            + rendered_unit(unit, config).chars().count()
+    })
+}
+
+/// Render a count, with a config that already includes the format string options.
+pub(crate) fn fmt_count(
+    f: &mut dyn fmt::Write,
+    h: &HumanCountData<'_>,
+    config: &HumanConfig,
+) -> fmt::Result {
+    let HumanCountData { mut val, unit, .. } = h;
+    let unit = rendered_unit(unit, config);
+    if let Some(exact) = h.exact_or_whole() {
+        return fmt_exact(f, exact, unit, config);
+    }
+
+    let (spec, divisor) = spec(config.prefixes);
+    let (space, precision) = (config.separator(), config.scaled_precision());
+    if !val.is_finite() {
+        return utils::fmt_non_finite(f, val, config, format_args!("{}{}", space, unit));
+    }
+    if config.sub_units && val != 0. && !utils::fixed(val, precision, DECIMALS[0]).at_least(1) {
+        for (i, &scale) in SUB_SPEC.iter().enumerate() {
+            val *= 1000.;
+            let r = utils::fixed(val, precision, DECIMALS[0]);
+            match r.scaled {
+                0 if i == SUB_SPEC.len() - 1 => return write!(f, "{}{}{}", r, space, unit),
+                _ if r.at_least(1) || i == SUB_SPEC.len() - 1 => {
+                    return write!(f, "{}{}{}{}", r, space, scale, unit)
                 }
-                r => return write!(f, "{:.2}{}{}{}", r, SPACE, scale, unit),
+                _ => {}
             }
         }
+    }
 
-        write!(f, "{:.2}{}+{}", val, SPACE, unit)
+    for (&scale, &dec) in spec.iter().zip(DECIMALS) {
+        let r = utils::fixed(val, precision, dec);
+        if !r.at_least(divisor as u128) {
+            return write!(f, "{}{}{}{}", r, space, scale, unit);
+        }
+        val /= divisor;
     }
+
+    // beyond the last prefix, scientific notation with the original value.
+    let val = h.val;
+    let exp = utils::exponent(val);
+    let (m, exp) = match utils::fixed(val / 10f64.powi(exp), precision, 2) {
+        m if m.at_least(10) => (
+            utils::fixed(val / 10f64.powi(exp + 1), precision, 2),
+            exp + 1,
+        ),
+        m => (m, exp),
+    };
+    write!(f, "{}e{}{}{}", m, exp, space, unit)
+}
+
+/// Render an exact integer with the same prefixes and rounding as the f64 path, but without
+/// its precision loss.
+fn fmt_exact(
+    f: &mut dyn fmt::Write,
+    exact: Exact,
+    unit: &str,
+    config: &HumanConfig,
+) -> fmt::Result {
+    let Exact { neg, mag } = exact;
+    let (spec, divisor) = spec(config.prefixes);
+    let (space, precision) = (config.separator(), config.scaled_precision());
+    let (divisor, mut div) = (divisor as u128, 1);
+    for (&scale, &dec) in spec.iter().zip(DECIMALS) {
+        let r = utils::fixed_exact(neg, mag, div, precision, dec);
+        if !r.at_least(divisor) {
+            return write!(f, "{}{}{}{}", r, space, scale, unit);
+        }
+        div *= divisor;
+    }
+
+    // beyond the last prefix, scientific notation; the carry can't happen with 39 digits.
+    let exp = mag.ilog10();
+    let (m, exp) = match utils::fixed_exact(neg, mag, 10u128.pow(exp), precision, 2) {
+        m if m.at_least(10) => {
+            let m = utils::fixed_exact(neg, mag, 10u128.pow(exp + 1), precision, 2);
+            (m, exp + 1)
+        }
+        m => (m, exp),
+    };
+    write!(f, "{}e{}{}{}", m, exp, space, unit)
 }
 
 impl Debug for HumanCountData<'_> {
@@ -47,6 +352,47 @@ impl Debug for HumanCountData<'_> {
     }
 }
 
+impl HumanCountData<'_> {
+    /// The exact integer of this count, which whole values also have, so they render and
+    /// compare alike however they were created.
+    fn exact_or_whole(&self) -> Option<Exact> {
+        self.exact.or_else(|| Exact::from_whole(self.val))
+    }
+
+    fn key(&self) -> utils::Key {
+        utils::Key {
+            val: self.val,
+            exact: self.exact_or_whole(),
+        }
+    }
+}
+
+/// Exact values compare exactly, just like they render, and all NaN values are equal, so it is
+/// also an `Eq` and `Hash` key.
+impl PartialEq for HumanCountData<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key() && self.unit == other.unit
+    }
+}
+
+impl Eq for HumanCountData<'_> {}
+
+impl Hash for HumanCountData<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+        self.unit.hash(state);
+    }
+}
+
+impl PartialOrd for HumanCountData<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.key().partial_cmp(&other.key()) {
+            Some(Ordering::Equal) => self.unit().partial_cmp(other.unit()),
+            ord => ord,
+        }
+    }
+}
+
 impl PartialEq<HumanCountData<'_>> for &str {
     fn eq(&self, other: &HumanCountData<'_>) -> bool {
         utils::display_compare(self, other)
@@ -60,6 +406,7 @@ impl PartialEq<&str> for HumanCountData<'_> {
 }
 
 #[cfg(all(test, not(any(feature = "1024", feature = "iec", feature = "space"))))]
+#[allow(clippy::needless_borrows_for_generic_args)] // the flexibility tests predate this lint.
 mod tests {
     use crate::HumanCount;
 
@@ -79,13 +426,34 @@ mod tests {
         assert_eq!("18.45EB", u64::MAX.human_count_bytes());
         assert_eq!("9.22EB", i64::MAX.human_count_bytes());
         assert_eq!("-9.22EB", i64::MIN.human_count_bytes());
-        assert_eq!("340282366920.94+B", u128::MAX.human_count_bytes());
+        assert_eq!("3.4e38B", u128::MAX.human_count_bytes());
+    }
+
+    #[test]
+    fn beyond_yotta() {
+        use crate::{HumanConfig, Prefixes};
+        let iec = HumanConfig::new().prefixes(Prefixes::Iec);
+        assert_eq!("1.23RB", 1234e24.human_count_bytes());
+        assert_eq!("1QB", 1e30.human_count_bytes());
+        assert_eq!("999.99QB", 999.99e30.human_count_bytes());
+        assert_eq!("1e33B", 999.999e30.human_count_bytes());
+        assert_eq!("1.23e35B", 1.2345e35.human_count_bytes());
+        assert_eq!("-1.7e38B", i128::MIN.human_count_bytes());
+        assert_eq!("1QiB", 2f64.powi(100).human_count_bytes().with_config(iec));
+        assert_eq!("1RiB", 2f64.powi(90).human_count_bytes().with_config(iec));
+        assert_eq!("3.4e38B", u128::MAX.human_count_bytes().with_config(iec));
+        assert_eq!(
+            "3.4e38 B",
+            u128::MAX.human_count_bytes().with_config(iec.space(true))
+        );
     }
 
     #[test]
     fn flexibility() {
         assert_eq!("123MCrabs", 123e6.human_count("Crabs"));
+        #[cfg(feature = "alloc")]
         assert_eq!("123MCrabs", 123e6.human_count("Crabs".to_owned()));
+        #[cfg(feature = "alloc")]
         assert_eq!("123MCrabs", 123e6.human_count(&"Crabs".to_owned()));
         assert_eq!("123k🦀", 123e3.human_count("🦀"));
         assert_eq!("12.3k°C", 123e2.human_count("°C"));
@@ -105,6 +473,283 @@ mod tests {
     fn symmetric() {
         assert_eq!(123000_u64.human_count_bytes(), "123kB");
     }
+
+    #[test]
+    fn value() {
+        use crate::{HumanConfig, HumanCountData};
+        use std::collections::HashSet;
+        let h = HumanCountData::new(-0., "B");
+        assert_eq!(h, 0.human_count_bytes());
+        let nan = f64::NAN.human_count_bytes();
+        assert_eq!(nan, (-f64::NAN).human_count_bytes());
+        assert_eq!(Some(core::cmp::Ordering::Equal), nan.partial_cmp(&nan));
+        let set = HashSet::from([h, 0.human_count_bytes(), nan.clone(), nan]);
+        assert_eq!(2, set.len());
+
+        let (a, b) = ((1_u64 << 60) + 1, (1_u64 << 60) + 2);
+        let (ha, hb) = (a.human_count_bytes(), b.human_count_bytes());
+        assert_eq!(ha.val, hb.val);
+        assert!(ha != hb && ha < hb);
+        assert_eq!(2, HashSet::from([ha, hb.clone()]).len());
+        assert!(hb < (1u64 << 61).human_count_bytes() && hb > -(1i64 << 61).human_count_bytes());
+        assert_eq!(1000.human_count_bytes(), 1000.0.human_count_bytes());
+        let h = 123456
+            .human_count("it")
+            .with_config(HumanConfig::new().space(true));
+        assert_eq!(HumanConfig::new().space(true), h.config());
+        assert_eq!((123456., "it"), (h.value(), h.unit()));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn into_owned() {
+        let h = {
+            let unit = String::from("files");
+            123456.human_count(unit.as_str()).into_owned()
+        };
+        assert_eq!("123.5kfiles", h);
+    }
+
+    #[test]
+    fn config() {
+        use crate::{HumanConfig, Prefixes};
+        let si = HumanConfig::new().space(true);
+        let k = HumanConfig::new().prefixes(Prefixes::Si1024);
+        let iec = HumanConfig::new().prefixes(Prefixes::Iec);
+        assert_eq!("1.2 MB", 1234567.human_count_bytes().with_config(si));
+        assert_eq!("1.2KB", 1234.human_count_bytes().with_config(k));
+        assert_eq!("1.2MiB", 1234567.human_count_bytes().with_config(iec));
+        assert_eq!("1.15GiB", 1234567890.human_count_bytes().with_config(iec));
+        assert_eq!("1000B", 1000.human_count_bytes().with_config(iec));
+        assert_eq!("1.2MB", 1234567.human_count_bytes());
+        assert_eq!(
+            1234.human_count_bytes(),
+            1234.human_count_bytes().with_config(iec)
+        );
+    }
+
+    #[test]
+    fn parse() {
+        use crate::{HumanConfig, HumanCountData, HumanParseError, Prefixes};
+        let p = |s| HumanCountData::parse(s).map(|h| (h.val, h.unit.to_string()));
+        assert_eq!(Ok((43210000000., "B".to_owned())), p("43.21GB"));
+        assert_eq!(Ok((540500., "Packets".to_owned())), p("540.5kPackets"));
+        assert_eq!(Ok((74900000., "".to_owned())), p("74.9M"));
+        assert_eq!(Ok((48.1, "°C".to_owned())), p("48.1°C"));
+        assert_eq!(Ok((123000., "🦀".to_owned())), p(" 123 k🦀 "));
+        assert_eq!(Ok((-23., "B".to_owned())), p("-23B"));
+        assert_eq!(Ok((1288490188.8, "B".to_owned())), p("1.2GiB"));
+        assert_eq!(Ok((1024., "B".to_owned())), p("1KB"));
+        assert_eq!(Ok((0.5, "".to_owned())), p(".5"));
+        assert_eq!(Ok((2e27, "B".to_owned())), p("2RB"));
+        assert_eq!(Ok((1.5e30, "B".to_owned())), p("1.5QB"));
+        assert_eq!(Ok((2f64.powi(100), "B".to_owned())), p("1QiB"));
+        assert_eq!(Ok((3.4e38, "B".to_owned())), p("3.4e38B"));
+        assert_eq!(Ok((-1.2e-5, "eggs".to_owned())), p("-1.2e-5eggs"));
+        assert_eq!(Ok((5., "eggs".to_owned())), p("5eggs"));
+        assert_eq!(Ok((5e18, "B".to_owned())), p("5EB"));
+        assert_eq!(Err(HumanParseError::Empty), p("  "));
+        assert_eq!(Err(HumanParseError::InvalidNumber), p("kB"));
+        assert_eq!(Err(HumanParseError::InvalidNumber), p("-.B"));
+
+        let k = HumanConfig::new().prefixes(Prefixes::Si1024);
+        let h = HumanCountData::parse_with("2MB", k).unwrap();
+        assert_eq!(2097152., h.val);
+        assert_eq!("2MB", h);
+
+        #[cfg(feature = "alloc")]
+        let h: HumanCountData = "23.43GB".parse().unwrap();
+        #[cfg(feature = "alloc")]
+        assert_eq!(
+            23433454432_u64.human_count_bytes().to_string(),
+            h.to_string()
+        );
+    }
+
+    #[test]
+    fn exact() {
+        use crate::HumanThroughput;
+        assert_eq!("1.01GB", 1_005_000_000_u64.human_count_bytes());
+        assert_eq!("1.01GB", 1_005_000_000_f64.human_count_bytes());
+        assert_eq!("1GB", 1_004_999_999.5.human_count_bytes());
+        assert_eq!("-1.01GB", (-1_005_000_000_i64).human_count_bytes());
+        assert_eq!("1.23EB", 1_234_999_999_999_999_999_u64.human_count_bytes());
+        assert_eq!("18.45EB", u64::MAX.human_count_bytes());
+        assert_eq!("1.01GB/s", 1_005_000_000_u64.human_throughput_bytes());
+        assert_eq!("1e33B", (10_u128.pow(33) - 1).human_count_bytes());
+        assert_eq!("0B", 0_u8.human_count_bytes());
+    }
+
+    #[test]
+    fn precision() {
+        use crate::{HumanConfig, Precision};
+        let s3 = HumanConfig::new().precision(Precision::Significant(3));
+        let d2 = HumanConfig::new().precision(Precision::Decimals(2));
+        assert_eq!("1.20GB", 1.2e9.human_count_bytes().with_config(s3));
+        assert_eq!("12.0GB", 12e9.human_count_bytes().with_config(s3));
+        assert_eq!("120GB", 120e9.human_count_bytes().with_config(s3));
+        assert_eq!("1.00TB", 999.6e9.human_count_bytes().with_config(s3));
+        assert_eq!("10.0kB", 9996.human_count_bytes().with_config(s3));
+        assert_eq!("0.500B", 0.5.human_count_bytes().with_config(s3));
+        assert_eq!("3.40e38B", u128::MAX.human_count_bytes().with_config(s3));
+        assert_eq!("1.20GB", 1.2e9.human_count_bytes().with_config(d2));
+        assert_eq!("999.60GB", 999.6e9.human_count_bytes().with_config(d2));
+        assert_eq!(
+            "120.00GB",
+            120_000_000_000_u64.human_count_bytes().with_config(d2)
+        );
+        assert_eq!("0.00B", 0.human_count_bytes().with_config(d2));
+        assert_eq!("1.2kB", format!("{:.1}", 1234.human_count_bytes()));
+        assert_eq!("1.234000000kB", format!("{:.20}", 1234.human_count_bytes()));
+        assert_eq!(
+            "1.234kB",
+            format!("{:.3}", 1234.human_count_bytes().with_config(s3))
+        );
+        assert_eq!("1kB", format!("{:.0}", 1234.human_count_bytes()));
+        assert_eq!(
+            "1.2300mV",
+            format!(
+                "{:.4}",
+                0.00123.human_count("V").with_config(s3.sub_units(true))
+            )
+        );
+        assert_eq!("1.000000000kB", format!("{:.20}", 1000.human_count_bytes()));
+    }
+
+    #[test]
+    fn non_finite() {
+        use crate::HumanConfig;
+        assert_eq!("NaN", f64::NAN.human_count_bytes());
+        assert_eq!("∞B", f64::INFINITY.human_count_bytes());
+        assert_eq!("-∞B", f64::NEG_INFINITY.human_count_bytes());
+        assert_eq!("0B", (-0.).human_count_bytes());
+        assert_eq!("0B", (-1e-9).human_count_bytes());
+        let c = HumanConfig::new().nan("—").infinity("inf").space(true);
+        assert_eq!("—", f64::NAN.human_count("V").with_config(c));
+        assert_eq!("-inf V", f64::NEG_INFINITY.human_count("V").with_config(c));
+        assert_eq!("   ∞B", format!("{:>5}", f64::INFINITY.human_count_bytes()));
+    }
+
+    #[test]
+    fn render() {
+        use crate::{HumanConfig, HumanCountData, HumanStr, Precision, Prefixes};
+        let s: HumanStr<8> = 1234.human_count("🦀").render().unwrap();
+        assert_eq!(("1.2k🦀", 8), (&*s, s.len()));
+        assert!(1234.human_count("🦀").render::<7>().is_none());
+        let mut buf = [0; 8];
+        assert_eq!(6, (-1234).human_count_bytes().write_to(&mut buf));
+        assert_eq!(b"-1.2kB", &buf[..6]);
+        assert_eq!(0, 1234.human_count("🦀🦀").write_to(&mut buf));
+
+        let worst = HumanConfig::new()
+            .prefixes(Prefixes::Iec)
+            .space(true)
+            .precision(Precision::Decimals(9))
+            .fixed_width(true);
+        let h = f64::NEG_INFINITY.human_count_bare().with_config(worst);
+        assert_eq!(HumanCountData::MAX_LEN, h.to_string().len());
+        let h = (-1023.9999999 * 1024f64.powi(8)).human_count_bare();
+        assert_eq!(
+            "-1023.999999900 Yi",
+            h.with_config(worst).render::<18>().unwrap()
+        );
+    }
+
+    #[test]
+    fn padding() {
+        let h = || 1234.human_count_bytes();
+        assert_eq!("   1.2kB", format!("{:>8}", h()));
+        assert_eq!("1.2kB   ", format!("{:8}", h()));
+        assert_eq!("1.2kB   ", format!("{:<8}", h()));
+        assert_eq!("_1.2kB__", format!("{:_^8}", h()));
+        assert_eq!("1.2kB", format!("{:3}", h()));
+        assert_eq!("  +1.2kB", format!("{:>+8}", h()));
+        assert_eq!("-1.2kB", format!("{:+}", (-1234).human_count_bytes()));
+        assert_eq!("  1.23kB", format!("{:>8.2}", h()));
+        assert_eq!("·····123k🦀", format!("{:·>10}", 123e3.human_count("🦀")));
+    }
+
+    #[test]
+    fn bits() {
+        use crate::{BitNotation, HumanConfig, HumanCountData};
+        let bps = HumanConfig::new().bit_notation(BitNotation::Bps);
+        assert_eq!("1.2Mbit", 1234567.human_count_bits());
+        assert_eq!("1.2Mb", 1234567.human_count_bits().with_config(bps));
+        assert_eq!("9.9Mbit", 1234567.human_count_bytes().into_bits());
+        assert_eq!("2.72e39bit", u128::MAX.human_count_bytes().into_bits());
+        assert_eq!("12kbit", 1.5e3.human_count_bytes().into_bits());
+        assert_eq!("1b", 1.human_count_bits().with_config(bps));
+        assert_eq!("1.2MB", 1234567.human_count("B").with_config(bps));
+        let live = bps.fixed_width(true);
+        assert_eq!(" 12.35Mb", 12345678.human_count_bits().with_config(live));
+        let h = HumanCountData::parse("1.2Mb").unwrap();
+        assert_eq!((1.2e6, "bit"), (h.val, h.unit()));
+        assert_eq!(HumanCountData::parse("1.2Mbit"), Ok(h));
+    }
+
+    #[test]
+    fn fixed_width() {
+        use crate::{HumanConfig, Precision, Prefixes};
+        let c = HumanConfig::new().fixed_width(true);
+        assert_eq!("   1.00B", 1.human_count_bytes().with_config(c));
+        assert_eq!("  9.99MB", 9990000.human_count_bytes().with_config(c));
+        assert_eq!(" 10.00MB", 10e6.human_count_bytes().with_config(c));
+        assert_eq!("999.99MB", 999990000.human_count_bytes().with_config(c));
+        assert_eq!("  1.00GB", 999995000.human_count_bytes().with_config(c));
+        assert_eq!("-10.00MB", (-10e6).human_count_bytes().with_config(c));
+        assert_eq!(
+            "10.00MB  ",
+            format!("{:9}", 10e6.human_count_bytes().with_config(c))
+        );
+        let iec = c.prefixes(Prefixes::Iec).space(true);
+        assert_eq!("   1.00 KiB", 1024.human_count_bytes().with_config(iec));
+        assert_eq!("1023.00 KiB", 1047552.human_count_bytes().with_config(iec));
+        let s3 = c.precision(Precision::Significant(3));
+        assert_eq!(" 1.23MB", 1234567.human_count_bytes().with_config(s3));
+        assert_eq!("  123MB", 123e6.human_count_bytes().with_config(s3));
+        assert_eq!(" 0.123B", 0.123.human_count_bytes().with_config(s3));
+        assert_eq!("0.000000123B", 1.23e-7.human_count_bytes().with_config(s3));
+        let sub = s3.sub_units(true);
+        assert_eq!("1.23MB", 1234567.human_count_bytes().with_config(sub));
+        assert_eq!(" 123mB", 0.123.human_count_bytes().with_config(sub));
+        assert_eq!(
+            "  1kB",
+            format!("{:.0}", 1234.human_count_bytes().with_config(c))
+        );
+    }
+
+    #[test]
+    fn sub_units() {
+        use crate::{HumanConfig, HumanCountData};
+        let c = HumanConfig::new().sub_units(true);
+        assert_eq!("1.2mV", 0.00123.human_count("V").with_config(c));
+        assert_eq!("-1.2mV", (-0.00123).human_count("V").with_config(c));
+        assert_eq!("900mV", 0.9.human_count("V").with_config(c));
+        assert_eq!("1V", 0.96.human_count("V").with_config(c));
+        assert_eq!("1mV", 0.00099996.human_count("V").with_config(c));
+        assert_eq!("15.6µA", 0.0000156.human_count("A").with_config(c));
+        assert_eq!("1n", 1e-9.human_count("").with_config(c));
+        assert_eq!("3.5pF", 3.5e-12.human_count("F").with_config(c));
+        assert_eq!("0.4pF", 4e-13.human_count("F").with_config(c));
+        assert_eq!("0F", 1e-14.human_count("F").with_config(c));
+        assert_eq!("0F", 0.human_count("F").with_config(c));
+        assert_eq!("1.2°C", 1.23.human_count("°C").with_config(c));
+        assert_eq!("12.3k°C", 123e2.human_count("°C").with_config(c));
+        assert_eq!(
+            "1.2 mV",
+            0.00123.human_count("V").with_config(c.space(true))
+        );
+        assert_eq!("0V", 0.00123.human_count("V"));
+
+        let p = |s| HumanCountData::parse_with(s, c).map(|h| (h.val, h.unit.to_string()));
+        assert_eq!(Ok((0.0012, "V".to_owned())), p("1.2mV"));
+        assert_eq!(Ok((15.6e-6, "A".to_owned())), p("15.6µA"));
+        assert_eq!(Ok((15.6e-6, "A".to_owned())), p("15.6 uA"));
+        assert_eq!(Ok((3.5e-12, "F".to_owned())), p("3.5pF"));
+        assert_eq!(Ok((2e6, "B".to_owned())), p("2MB"));
+        let h = HumanCountData::parse("2mol").unwrap();
+        assert_eq!((2., "mol"), (h.val, h.unit()));
+    }
 }
 
 #[test]
@@ -116,5 +761,8 @@ fn serialize() -> Result<(), serde_json::Error> {
     assert_eq!(r#"{"val":123456.0,"unit":"X"}"#, &ser);
     let h2 = serde_json::from_str::<HumanCountData>(&ser)?;
     assert_eq!(h, h2);
+    let h = 1_005_000_000_u64.human_count_bytes();
+    let h2 = serde_json::from_str::<HumanCountData>(&serde_json::to_string(&h)?)?;
+    assert_eq!(("1.01GB", h.to_string()), ("1.01GB", h2.to_string()));
     Ok(())
 }
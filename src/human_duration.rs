@@ -1,6 +1,16 @@
-use super::{HumanDuration, HumanDurationData};
-use crate::utils::{self, SPACE};
-use std::{fmt, time::Duration};
+use super::{
+    DurationStyle, HumanConfig, HumanDuration, HumanDurationData, HumanParseError, HumanStr,
+    Precision, TimeUnit,
+};
+use crate::parse;
+#[cfg(not(any(feature = "std", test)))]
+use crate::utils::Float;
+use crate::utils::{self, Exact, Fixed};
+use core::hash::{Hash, Hasher};
+use core::time::{Duration, TryFromFloatSecsError};
+use core::{cmp::Ordering, fmt, str::FromStr};
+#[cfg(feature = "std")]
+use std::time::SystemTime;
 
 const SPEC: &[(f64, f64, &str, usize)] = &[
     (1e3, 1e3, "ns", 1),
@@ -10,34 +20,670 @@ const SPEC: &[(f64, f64, &str, usize)] = &[
     // 1:01.1 (minutes in code, 1 decimal).
     // 1:01:01 (hours in code, 0 decimal).
 ];
+const LONG_SPEC: &[(&str, &str)] = &[
+    ("nanosecond", "nanoseconds"),
+    ("microsecond", "microseconds"),
+    ("millisecond", "milliseconds"),
+    ("second", "seconds"),
+];
+const UNITS: &[(f64, &str, &str, &str)] = &[
+    (365. * 86400., "y", "year", "years"), // years of 365 days.
+    (7. * 86400., "w", "week", "weeks"),
+    (86400., "d", "day", "days"),
+    (3600., "h", "hour", "hours"),
+    (60., "m", "minute", "minutes"),
+    (1., "s", "second", "seconds"),
+];
+pub const NANOS: u128 = 1_000_000_000;
+const ALIASES: &[(&str, &str)] = &[("us", "µs"), ("μs", "µs")]; // ASCII and greek mu.
+
+impl HumanDurationData {
+    /// The maximum length in bytes of any duration in any style, as long as the NaN and
+    /// infinity strings are the default ones.
+    pub const MAX_LEN: usize = 363;
+
+    /// Create a duration of a raw value in seconds, where negative ones are in the past for
+    /// the relative style.
+    #[cfg_attr(
+        not(any(feature = "1024", feature = "iec", feature = "space")),
+        doc = r#"
+
+```
+use human_repr::HumanDurationData;
+assert_eq!("1:04:48", HumanDurationData::new(3888.));
+```
+"#
+    )]
+    pub fn new(secs: f64) -> Self {
+        HumanDurationData {
+            val: secs,
+            exact: None,
+            config: HumanConfig::new(),
+        }
+    }
+
+    /// Render this duration with a custom [`HumanConfig`], instead of the default one.
+    pub fn with_config(mut self, config: HumanConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Render this duration inline, without allocating, if it fits in `N` bytes.
+    pub fn render<const N: usize>(&self) -> Option<HumanStr<N>> {
+        HumanStr::render(self)
+    }
+
+    /// Render this duration into a buffer, returning the number of bytes written, which is zero
+    /// when it doesn't fit.
+    pub fn write_to(&self, buf: &mut [u8]) -> usize {
+        utils::write_to(buf, self)
+    }
+
+    /// Parse a human duration back, accepting all formats the Display impl generates for finite
+    /// values, like `"15.6µs"`, `"3.44s"`, `"19:20.4"`, `"1:04:48"`, `"11d 13:46:40"`,
+    /// `"1h 4m 48s"`, or `"1 hour, 4 minutes and 48 seconds"`, relative times like
+    /// `"3 minutes ago"`, or ISO 8601 ones like `"PT1H4M48S"`, as well as `"us"` for microseconds.
+    #[cfg_attr(
+        not(any(feature = "1024", feature = "iec", feature = "space")),
+        doc = r#"
+
+```
+use human_repr::{HumanDuration, HumanDurationData};
+
+let h = HumanDurationData::parse("1:04:48")?;
+assert_eq!(3888.human_duration(), h);
+
+let h: HumanDurationData = "15.6us".parse()?;
+assert_eq!("15.6µs", h);
+# Ok::<(), human_repr::HumanParseError>(())
+```
+"#
+    )]
+    pub fn parse(s: &str) -> Result<Self, HumanParseError> {
+        let s = s.trim();
+        if s.strip_prefix(['-', '+']).unwrap_or(s).starts_with('P') {
+            return Self::parse_iso8601(s);
+        }
+        let val = match s {
+            "just now" => 0.,
+            "yesterday" => -86400.,
+            "tomorrow" => 86400.,
+            _ => match (s.strip_prefix("in "), s.strip_suffix(" ago")) {
+                (Some(future), _) => parse_signed(future)?,
+                (_, Some(past)) => -parse_signed(past)?,
+                _ => parse_signed(s)?,
+            },
+        };
+        Ok(HumanDurationData {
+            val,
+            exact: None,
+            config: HumanConfig::new(),
+        })
+    }
+
+    /// Parse an ISO 8601 duration, like `"PT1H4M48.395S"` or `"P11DT13H46M40S"`, with an optional
+    /// sign and `.` as the decimal separator.
+    ///
+    /// Weeks and days are supported as exactly 7 and 1 days, but the calendar years and months
+    /// are not, as they do not have a fixed length.
+    #[cfg_attr(
+        not(any(feature = "1024", feature = "iec", feature = "space")),
+        doc = r#"
+
+```
+use human_repr::{HumanDuration, HumanDurationData};
+let h = HumanDurationData::parse_iso8601("PT1H4M48.395S")?;
+assert_eq!(3888.395.human_duration(), h);
+assert_eq!("1:04:48", h);
+# Ok::<(), human_repr::HumanParseError>(())
+```
+"#
+    )]
+    pub fn parse_iso8601(s: &str) -> Result<Self, HumanParseError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(HumanParseError::Empty);
+        }
+        let (neg, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let s = s.strip_prefix('P').ok_or(HumanParseError::InvalidFormat)?;
+        let (date, time) = match s.split_once('T') {
+            Some((_, "")) => return Err(HumanParseError::InvalidFormat),
+            Some((date, time)) => (date, time),
+            None => (s, ""),
+        };
+        if s.is_empty() || s.contains(|c: char| c.is_whitespace() || c == '-' || c == '+') {
+            return Err(HumanParseError::InvalidFormat);
+        }
+
+        let date_spec: &[_] = &[('W', 7. * 86400.), ('D', 86400.)];
+        let time_spec: &[_] = &[('H', 3600.), ('M', 60.), ('S', 1.)];
+        let mut val = 0.;
+        for (mut s, mut spec) in [(date, date_spec), (time, time_spec)] {
+            while !s.is_empty() {
+                let (num, rest) = parse::split_number(s)?;
+                let designator = rest.chars().next().ok_or(HumanParseError::InvalidFormat)?;
+                let i = spec
+                    .iter()
+                    .position(|&(d, _)| d == designator)
+                    .ok_or(HumanParseError::InvalidUnit)?;
+                val += parse::parse_scaled(num, 0) * spec[i].1;
+                spec = &spec[i + 1..];
+                s = &rest[designator.len_utf8()..];
+            }
+        }
+        Ok(HumanDurationData {
+            val: if neg { -val } else { val },
+            exact: None,
+            config: HumanConfig::new(),
+        })
+    }
+
+    /// Create a relative time of an event to `now`, which is negative when it is in the past, and
+    /// renders as such.
+    #[cfg_attr(
+        not(any(feature = "1024", feature = "iec", feature = "space")),
+        doc = r#"
+
+```
+use human_repr::HumanDurationData;
+use std::time::{Duration, SystemTime};
+
+let now = SystemTime::now();
+let event = now - Duration::from_secs(190);
+assert_eq!("3 minutes ago", HumanDurationData::relative(event, now));
+```
+"#
+    )]
+    #[cfg(feature = "std")]
+    pub fn relative(event: SystemTime, now: SystemTime) -> Self {
+        let (neg, d) = match event.duration_since(now) {
+            Ok(d) => (false, d),
+            Err(e) => (true, e.duration()),
+        };
+        let secs = d.as_secs_f64();
+        HumanDurationData {
+            val: if neg { -secs } else { secs },
+            exact: Some(Exact::new(neg, d.as_nanos())),
+            config: HumanConfig::new().duration_style(DurationStyle::Relative),
+        }
+    }
+
+    /// The raw value of this duration, in seconds.
+    pub fn value(&self) -> f64 {
+        self.val
+    }
+
+    /// The [`HumanConfig`] this duration is rendered with.
+    pub fn config(&self) -> HumanConfig {
+        self.config
+    }
+}
+
+/// Parse all the formats, with an optional sign.
+fn parse_signed(s: &str) -> Result<f64, HumanParseError> {
+    let s = s.strip_prefix("about ").unwrap_or(s);
+    let (neg, body) = match s.strip_prefix('-') {
+        Some(body) => (true, body),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    if body.starts_with(['-', '+']) {
+        return Err(HumanParseError::InvalidNumber);
+    }
+
+    let val = match body.contains(':') {
+        true => parse_clock(body)?,
+        false => parse_units(body)?,
+    };
+    Ok(if neg { -val } else { val })
+}
+
+/// Parse the `SS[.ss]scale`, compound, and long formats, e.g. `"15.6µs"`, `"1h 4m 48s"`, or
+/// `"1 hour, 4 minutes and 48 seconds"`.
+fn parse_units(mut s: &str) -> Result<f64, HumanParseError> {
+    let mut total = 0.;
+    loop {
+        if s.starts_with(['-', '+']) {
+            return Err(HumanParseError::InvalidNumber);
+        }
+        let (num, rest) = parse::split_number(s)?;
+        let len = rest
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(rest.len());
+        let (exp, factor) = find_scale(&rest[..len]).ok_or(HumanParseError::InvalidUnit)?;
+        total += parse::parse_scaled(num, exp) * factor;
+        s = rest[len..]
+            .trim_start()
+            .trim_start_matches(',')
+            .trim_start();
+        s = s.strip_prefix("and ").unwrap_or(s).trim_start();
+        if s.is_empty() {
+            return Ok(total);
+        }
+    }
+}
+
+/// Find how to convert a value with the given scale into seconds: a power of ten and a factor.
+fn find_scale(scale: &str) -> Option<(i32, f64)> {
+    let scale = ALIASES
+        .iter()
+        .find_map(|&(alias, to)| (alias == scale).then_some(to))
+        .unwrap_or(scale);
+    let found = SPEC
+        .iter()
+        .zip(LONG_SPEC)
+        .position(|(&(_, _, short, _), &(one, many))| {
+            scale == short || scale == one || scale == many
+        });
+    if let Some(i) = found {
+        return Some((3 * i as i32 - 9, 1.));
+    }
+    UNITS
+        .iter()
+        .find(|&&(_, short, one, many)| scale == short || scale == one || scale == many)
+        .map(|&(secs, ..)| (0, secs))
+}
+
+/// Parse the `M:SS[.s]` and `H:MM:SS` formats, with optional larger units like `1w 4d 13:46:40`.
+fn parse_clock(s: &str) -> Result<f64, HumanParseError> {
+    let (large, s) = s.rsplit_once(' ').unwrap_or(("", s));
+    let large = large.split_whitespace().try_fold(0., |acc, token| {
+        let (num, scale) = parse::split_number(token)?;
+        let &(secs, ..) = UNITS[..3]
+            .iter()
+            .find(|&&(_, unit, ..)| unit == scale)
+            .ok_or(HumanParseError::InvalidUnit)?;
+        Ok::<_, HumanParseError>(acc + parse::parse_scaled(num, 0) * secs)
+    })?;
+    parse_hms(s).map(|secs| large + secs)
+}
+
+fn parse_hms(s: &str) -> Result<f64, HumanParseError> {
+    let fields = s.split(':').count();
+    if fields > 3 {
+        return Err(HumanParseError::InvalidFormat);
+    }
+
+    s.split(':').enumerate().try_fold(0., |acc, (i, field)| {
+        let last = i == fields - 1;
+        let valid = field
+            .bytes()
+            .all(|c| c.is_ascii_digit() || (last && c == b'.'));
+        let val = match parse::split_number(field) {
+            Ok((num, "")) if valid => parse::parse_scaled(num, 0),
+            _ => return Err(HumanParseError::InvalidFormat),
+        };
+        match i > 0 && val >= 60. {
+            true => Err(HumanParseError::InvalidFormat),
+            false => Ok(acc * 60. + val),
+        }
+    })
+}
+
+impl FromStr for HumanDurationData {
+    type Err = HumanParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        HumanDurationData::parse(s)
+    }
+}
 
 impl fmt::Display for HumanDurationData {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let HumanDurationData { mut val } = self;
-        val *= 1e9;
-        for &(size, next, scale, dec) in SPEC {
-            match utils::rounded(val, dec) {
-                r if r.abs() >= size => val /= next,
-                r if r.fract() == 0. => return write!(f, "{:.0}{}{}", r, SPACE, scale),
-                r if (r * 10.).fract() == 0. => return write!(f, "{:.1}{}{}", r, SPACE, scale),
-                r => return write!(f, "{:.2}{}{}", r, SPACE, scale),
+        let config = self.config.with_formatter(f);
+        let width = fixed_width(&config);
+        utils::pad(f, width, |f| fmt_duration(f, self, &config))
+    }
+}
+
+/// The width of any duration below one day in the fixed-width mode, which is only supported in
+/// the clock style.
+pub(crate) fn fixed_width(config: &HumanConfig) -> Option<usize> {
+    if !config.fixed_width || config.duration_style != DurationStyle::Clock {
+        return None;
+    }
+    let scaled =
+        utils::num_width(config.scaled_precision(), 3, false) + config.separator().len() + 2;
+    let clock = match config.precision {
+        Precision::Decimals(_) => utils::num_width(config.precision, 8, false),
+        _ => "00:00:00".len(),
+    };
+    Some(scaled.max(clock))
+}
+
+/// Render a duration, with a config that already includes the format string options.
+pub(crate) fn fmt_duration(
+    f: &mut dyn fmt::Write,
+    h: &HumanDurationData,
+    config: &HumanConfig,
+) -> fmt::Result {
+    let (val, exact) = (h.val, h.exact_or_whole());
+    if !val.is_finite() {
+        return utils::fmt_non_finite(f, val, config, format_args!(""));
+    }
+    // the exact nanoseconds, or the nearest ones, which are always used from one minute onward.
+    let nanos = match exact {
+        Some(Exact { neg, mag }) => Nanos { neg, head: 0., mag },
+        None => Nanos::nearest(val, config),
+    };
+    let style = match config.duration_style {
+        DurationStyle::Relative => return fmt_relative(f, nanos, config),
+        DurationStyle::Iso8601 => return fmt_iso8601(f, nanos, config),
+        DurationStyle::Clock => PartsStyle::Clock,
+        DurationStyle::Compound => PartsStyle::Compound,
+        DurationStyle::Long => PartsStyle::Long,
+    };
+
+    let (space, precision) = (config.separator(), config.scaled_precision());
+    let (mut val, mut div) = (val * 1e9, 1);
+    for (&(size, next, scale, dec), &(one, many)) in SPEC.iter().zip(LONG_SPEC) {
+        let r = match exact {
+            Some(Exact { neg, mag }) => utils::fixed_exact(neg, mag, div, precision, dec),
+            None => utils::fixed(val, precision, dec),
+        };
+        if r.at_least(size as u128) {
+            (val, div) = (val / next, div * next as u128);
+            continue;
+        }
+
+        return match style {
+            PartsStyle::Long if r.is_one() => write!(f, "{} {}", r, one),
+            PartsStyle::Long => write!(f, "{} {}", r, many),
+            _ => write!(f, "{}{}{}", r, space, scale),
+        };
+    }
+
+    match style {
+        PartsStyle::Clock => fmt_clock(f, nanos, config),
+        PartsStyle::Compound => fmt_compound(f, nanos, config),
+        PartsStyle::Long => fmt_long(f, nanos, config),
+    }
+}
+
+/// The styles that render durations below one minute with a single scale, and in parts from
+/// one minute onward.
+#[derive(Clone, Copy)]
+enum PartsStyle {
+    Clock,
+    Compound,
+    Long,
+}
+
+/// An absolute duration in nanoseconds and its sign, where the durations beyond the u128
+/// nanoseconds are split at the first unit, whose whole count is kept as a float.
+#[derive(Clone, Copy)]
+struct Nanos {
+    neg: bool,
+    /// The whole first units, only used beyond the u128 nanoseconds.
+    head: f64,
+    mag: u128,
+}
+
+impl Nanos {
+    /// The nearest nanoseconds of a finite value.
+    fn nearest(val: f64, config: &HumanConfig) -> Self {
+        let (neg, abs) = (val < 0., val.abs());
+        if abs * 1e9 < u128::MAX as f64 {
+            let mag = (abs * 1e9).round() as u128;
+            return Nanos {
+                neg: neg && mag != 0,
+                head: 0.,
+                mag,
+            };
+        }
+        // the ISO 8601 style splits at days, as it never uses weeks or years.
+        let first = match config.duration_style {
+            DurationStyle::Iso8601 => first_unit(config).max(2),
+            _ => first_unit(config),
+        };
+        let (unit, ..) = UNITS[first];
+        let rest = abs % unit;
+        Nanos {
+            neg,
+            head: ((abs - rest) / unit).round(),
+            mag: (rest * 1e9).round() as u128,
+        }
+    }
+}
+
+/// A count of a unit, which is a float only for the first unit of durations beyond the u128
+/// nanoseconds.
+#[derive(Clone, Copy)]
+enum Count {
+    Int(u128),
+    Float(f64),
+}
+
+impl Count {
+    /// The count of the first unit, including the head of the duration.
+    fn first(head: f64, n: u128) -> Self {
+        match head > 0. {
+            true => Count::Float(head + n as f64),
+            false => Count::Int(n),
+        }
+    }
+
+    fn is_one(self) -> bool {
+        matches!(self, Count::Int(1))
+    }
+}
+
+impl fmt::Display for Count {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Count::Int(n) => write!(f, "{}", n),
+            Count::Float(n) => write!(f, "{:.0}", n),
+        }
+    }
+}
+
+/// The index of the largest unit in UNITS allowed by the config.
+fn first_unit(config: &HumanConfig) -> usize {
+    match config.max_unit {
+        TimeUnit::Years => 0,
+        TimeUnit::Weeks => 1,
+        TimeUnit::Days => 2,
+        TimeUnit::Hours => 3,
+    }
+}
+
+/// Render the `M:SS[.s]` and `H:MM:SS` formats, with larger units up to the config max unit.
+///
+/// The seconds have one decimal only below one hour, unless the precision sets the decimals,
+/// which is kept even when zero in the fixed-width mode.
+fn fmt_clock(f: &mut dyn fmt::Write, nanos: Nanos, config: &HumanConfig) -> fmt::Result {
+    if nanos.neg {
+        f.write_str("-")?;
+    }
+    let (dec, trim) = match config.precision {
+        Precision::Decimals(_) => (config.precision.decimals(1, 0), false),
+        _ => (1, true),
+    };
+    let pow = 10u128.pow(dec as u32);
+    let units = utils::div_rounded(nanos.mag, NANOS / pow, 0);
+    let (m, s) = (units / (60 * pow), units % (60 * pow));
+    let zero = if s < 10 * pow { "0" } else { "" };
+    let secs = Fixed {
+        neg: false,
+        scaled: if trim && m >= 60 { s / pow * pow } else { s },
+        dec,
+        trim,
+    };
+    if m < 60 && nanos.head == 0. {
+        let secs = Fixed {
+            trim: trim && !config.fixed_width,
+            ..secs
+        };
+        return write!(f, "{}:{}{}", m, zero, secs);
+    }
+
+    let (h, m) = (m / 60, m % 60);
+    if config.max_unit == TimeUnit::Hours || (h < 24 && nanos.head == 0.) {
+        let h = Count::first(nanos.head, h);
+        return write!(f, "{}:{:02}:{}{}", h, m, zero, secs);
+    }
+    let (first, mut large) = (first_unit(config), h / 24 * 86400);
+    for (i, &(secs, scale, ..)) in UNITS.iter().enumerate().take(3).skip(first) {
+        let secs = secs as u128;
+        let head = if i == first { nanos.head } else { 0. };
+        if head > 0. || large >= secs {
+            write!(f, "{}{} ", Count::first(head, large / secs), scale)?;
+            large %= secs;
+        }
+    }
+    write!(f, "{:02}:{:02}:{}{}", h % 24, m, zero, secs)
+}
+
+/// The nonzero parts of a duration in UNITS, as `(count, index)` pairs.
+struct Parts {
+    parts: [(Count, usize); UNITS.len()],
+    len: usize,
+    /// Whether whole seconds were lost by rounding the last part.
+    approx: bool,
+}
+
+impl Parts {
+    /// Split an absolute duration in nanoseconds into parts, up to the config max unit and max
+    /// parts, rounding the last one.
+    fn new(nanos: Nanos, config: &HumanConfig) -> Self {
+        let (first, head, nanos) = (first_unit(config), nanos.head, nanos.mag);
+        let top = match head > 0. {
+            true => Some(0),
+            false => UNITS[first..]
+                .iter()
+                .position(|&(secs, ..)| nanos >= secs as u128 * NANOS),
+        };
+        let top = first + top.unwrap_or(UNITS.len() - 1 - first);
+        let last = (top + config.max_parts.max(1) as usize - 1).min(UNITS.len() - 1);
+        let step = UNITS[last].0 as u128;
+        let mut rest = utils::div_rounded(nanos, step * NANOS, 0);
+        let approx = rest * step != utils::div_rounded(nanos, NANOS, 0);
+
+        let mut parts = Parts {
+            parts: [(Count::Int(0), 0); UNITS.len()],
+            len: 0,
+            approx,
+        };
+        for (i, &(secs, ..)) in UNITS.iter().enumerate().take(last + 1).skip(first) {
+            let size = secs as u128 / step;
+            let head = if i == first { head } else { 0. };
+            if head > 0. || rest >= size {
+                parts.parts[parts.len] = (Count::first(head, rest / size), i);
+                parts.len += 1;
+                rest %= size;
             }
         }
+        parts
+    }
 
-        val = utils::rounded(val, 1);
-        let (m, s) = (val / 60., val % 60.);
-        match m < 60. {
-            true => match s {
-                _ if s.fract() == 0. => write!(f, "{}:{:02}", m.trunc(), s),
-                _ => write!(f, "{}:{:04}", m.trunc(), utils::rounded(s, 1)),
-            },
-            false => write!(
-                f,
-                "{}:{:02}:{:02}",
-                (m / 60.).trunc(),
-                (m % 60.).trunc(),
-                s.trunc()
-            ),
+    fn iter(&self) -> impl Iterator<Item = &(Count, usize)> {
+        self.parts[..self.len].iter()
+    }
+}
+
+/// Render the compound format, e.g. `1h 4m 48s`, rounding the last part.
+fn fmt_compound(f: &mut dyn fmt::Write, nanos: Nanos, config: &HumanConfig) -> fmt::Result {
+    if nanos.neg {
+        f.write_str("-")?;
+    }
+    for (i, &(n, unit)) in Parts::new(nanos, config).iter().enumerate() {
+        let sep = if i == 0 { "" } else { config.separator() };
+        write!(f, "{}{}{}", sep, n, UNITS[unit].1)?;
+    }
+    Ok(())
+}
+
+/// Render the long format, e.g. `1 hour, 4 minutes and 48 seconds`, rounding the last part.
+fn fmt_long(f: &mut dyn fmt::Write, nanos: Nanos, config: &HumanConfig) -> fmt::Result {
+    let parts = Parts::new(nanos, config);
+    if parts.approx {
+        f.write_str("about ")?;
+    }
+    if nanos.neg {
+        f.write_str("-")?;
+    }
+    for (i, &(n, unit)) in parts.iter().enumerate() {
+        let sep = match i {
+            0 => "",
+            _ if i == parts.len - 1 => " and ",
+            _ => ", ",
+        };
+        let (_, _, one, many) = UNITS[unit];
+        write!(f, "{}{} {}", sep, n, if n.is_one() { one } else { many })?;
+    }
+    Ok(())
+}
+
+/// Render the relative format, e.g. `3 minutes ago` or `in 2 hours`, with a single rounded part.
+fn fmt_relative(f: &mut dyn fmt::Write, nanos: Nanos, config: &HumanConfig) -> fmt::Result {
+    let Nanos { neg, head, mag } = nanos;
+    if head == 0. && mag < config.just_now.as_nanos() {
+        return f.write_str("just now");
+    }
+    let single = HumanConfig {
+        max_parts: 1,
+        ..*config
+    };
+    let parts = Parts::new(nanos, &single);
+    let Some(&(n, unit)) = parts.iter().next() else {
+        return f.write_str("just now");
+    };
+    let (_, scale, one, many) = UNITS[unit];
+    // the rounded days, so the same ones are never both `yesterday` and `1 day ago`.
+    if let (Count::Int(days), "d") = (n, scale) {
+        if days * 86400 * NANOS < config.yesterday.as_nanos() {
+            return f.write_str(if neg { "yesterday" } else { "tomorrow" });
+        }
+    }
+    let name = if n.is_one() { one } else { many };
+    match neg {
+        true => write!(f, "{} {} ago", n, name),
+        false => write!(f, "in {} {}", n, name),
+    }
+}
+
+/// Render the ISO 8601 format, e.g. `PT1H4M48.395S`, with days when allowed by the config max unit.
+fn fmt_iso8601(f: &mut dyn fmt::Write, nanos: Nanos, config: &HumanConfig) -> fmt::Result {
+    if nanos.neg {
+        f.write_str("-")?;
+    }
+    let Nanos { head, mut mag, .. } = nanos;
+    let (days, day_head, hour_head) = match config.max_unit {
+        TimeUnit::Hours => (0, 0., head),
+        _ => (mag / (86400 * NANOS), head, 0.),
+    };
+    mag -= days * 86400 * NANOS;
+    f.write_str("P")?;
+    let has_days = day_head > 0. || days > 0;
+    if has_days {
+        write!(f, "{}D", Count::first(day_head, days))?;
+    }
+    if mag == 0 && has_days {
+        return Ok(());
+    }
+
+    f.write_str("T")?;
+    let (h, m) = (mag / (3600 * NANOS), mag / (60 * NANOS) % 60);
+    let (s, frac) = (mag / NANOS % 60, mag % NANOS);
+    let has_hours = hour_head > 0. || h > 0;
+    if has_hours {
+        write!(f, "{}H", Count::first(hour_head, h))?;
+    }
+    if m > 0 {
+        write!(f, "{}M", m)?;
+    }
+    match frac {
+        0 if s == 0 && (has_hours || m > 0) => Ok(()),
+        0 => write!(f, "{}S", s),
+        _ => {
+            let (mut frac, mut digits) = (frac, 9);
+            while frac % 10 == 0 {
+                frac /= 10;
+                digits -= 1;
+            }
+            write!(f, "{}.{:0digits$}S", s, frac, digits = digits)
         }
     }
 }
@@ -52,6 +698,44 @@ impl fmt::Debug for HumanDurationData {
     }
 }
 
+impl HumanDurationData {
+    /// The exact nanoseconds of this duration, which whole values also have, so they render and
+    /// compare alike however they were created.
+    fn exact_or_whole(&self) -> Option<Exact> {
+        self.exact
+            .or_else(|| Exact::from_whole(self.val)?.checked_mul(NANOS))
+    }
+
+    fn key(&self) -> utils::Key {
+        utils::Key {
+            val: self.val,
+            exact: self.exact_or_whole(),
+        }
+    }
+}
+
+/// Exact values compare exactly, just like they render, and all NaN values are equal, so it is
+/// also an `Eq` and `Hash` key.
+impl PartialEq for HumanDurationData {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for HumanDurationData {}
+
+impl Hash for HumanDurationData {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl PartialOrd for HumanDurationData {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.key().partial_cmp(&other.key())
+    }
+}
+
 impl PartialEq<HumanDurationData> for &str {
     fn eq(&self, other: &HumanDurationData) -> bool {
         utils::display_compare(self, other)
@@ -66,7 +750,24 @@ impl PartialEq<&str> for HumanDurationData {
 
 impl From<Duration> for HumanDurationData {
     fn from(d: Duration) -> Self {
-        d.as_secs_f64().human_duration()
+        HumanDurationData {
+            val: d.as_secs_f64(),
+            exact: Some(Exact::new(false, d.as_nanos())),
+            config: HumanConfig::new(),
+        }
+    }
+}
+
+impl TryFrom<HumanDurationData> for Duration {
+    type Error = TryFromFloatSecsError;
+
+    fn try_from(h: HumanDurationData) -> Result<Self, Self::Error> {
+        if let Some(Exact { neg: false, mag }) = h.exact {
+            if let Ok(secs) = u64::try_from(mag / NANOS) {
+                return Ok(Duration::new(secs, (mag % NANOS) as u32));
+            }
+        }
+        Duration::try_from_secs_f64(h.val)
     }
 }
 
@@ -109,8 +810,14 @@ mod tests {
         assert_eq!("19:20.4", 1160.36.human_duration());
         assert_eq!("1:04:48", 3888.395.human_duration());
         assert_eq!("2:46:40", 10000u16.human_duration());
-        assert_eq!("27:46:40", 100000i64.human_duration());
-        assert_eq!("277:46:40", 1000000isize.human_duration());
+        assert_eq!("1d 03:46:40", 100000i64.human_duration());
+        assert_eq!("11d 13:46:40", 1000000isize.human_duration());
+        assert_eq!("-1:08.1", (-68.09).human_duration());
+        assert_eq!("-1:04:48", (-3888.395).human_duration());
+        assert_eq!("23:59:59", 86399.human_duration());
+        assert_eq!("1d 00:00:00", 86400.human_duration());
+        assert_eq!("-1d 00:00:01", (-86401).human_duration());
+        assert_eq!("365d 00:00:00", 31536000.human_duration());
     }
 
     #[test]
@@ -148,8 +855,8 @@ mod tests {
         assert_eq!("19:20.4", d!(1160, 350000000).human_duration());
         assert_eq!("1:04:48", d!(3888.395).human_duration());
         assert_eq!("2:46:40", d!(10000.).human_duration());
-        assert_eq!("27:46:40", d!(100000.).human_duration());
-        assert_eq!("277:46:40", d!(1000000, 1).human_duration());
+        assert_eq!("1d 03:46:40", d!(100000.).human_duration());
+        assert_eq!("11d 13:46:40", d!(1000000, 1).human_duration());
     }
 
     #[test]
@@ -165,6 +872,439 @@ mod tests {
     fn symmetric() {
         assert_eq!(1.human_duration(), "1s");
     }
+
+    #[test]
+    fn value() {
+        use crate::{DurationStyle, HumanConfig, HumanDurationData};
+        use std::collections::HashSet;
+        use std::time::Duration;
+        let h = HumanDurationData::new(-0.);
+        let copy = h;
+        assert_eq!(h, copy);
+        assert_eq!(h, Duration::ZERO.human_duration());
+        let nan = f64::NAN.human_duration();
+        assert_eq!(nan, (-f64::NAN).human_duration());
+        let set = HashSet::from([h, Duration::ZERO.human_duration(), nan, nan]);
+        assert_eq!(2, set.len());
+
+        let (a, b) = (
+            Duration::new(10_000_000_000, 1),
+            Duration::new(10_000_000_000, 2),
+        );
+        let (ha, hb) = (a.human_duration(), b.human_duration());
+        assert_eq!(ha.val, hb.val);
+        assert_ne!(ha, hb);
+        assert!(ha < hb);
+        assert_eq!(2, HashSet::from([ha, hb]).len());
+        assert_eq!(3888.human_duration(), 3888.0.human_duration());
+
+        let long = HumanConfig::new().duration_style(DurationStyle::Long);
+        let h = Duration::new(3888, 395_000_001)
+            .human_duration()
+            .with_config(long);
+        assert_eq!(long, h.config());
+        assert_eq!(Ok(Duration::new(3888, 395_000_001)), Duration::try_from(h));
+        assert_eq!(
+            Ok(Duration::from_millis(1500)),
+            HumanDurationData::new(1.5).try_into()
+        );
+        assert!(Duration::try_from(HumanDurationData::new(-1.)).is_err());
+    }
+
+    #[test]
+    fn parse() {
+        use crate::{HumanDurationData, HumanParseError};
+        let p = |s| HumanDurationData::parse(s).map(|h| h.val);
+        assert_eq!(Ok(1.8e-9), p("1.8ns"));
+        assert_eq!(Ok(15.6e-6), p("15.6µs"));
+        assert_eq!(Ok(15.6e-6), p("15.6us"));
+        assert_eq!(Ok(0.01), p("10ms"));
+        assert_eq!(Ok(3.44), p(" 3.44 s "));
+        assert_eq!(Ok(-1.), p("-1s"));
+        assert_eq!(Ok(60.), p("1:00"));
+        assert_eq!(Ok(68.1), p("1:08.1"));
+        assert_eq!(Ok(1160.4), p("19:20.4"));
+        assert_eq!(Ok(3888.), p("1:04:48"));
+        assert_eq!(Ok(1000000.), p("277:46:40"));
+        assert_eq!(Ok(1000000.), p("11d 13:46:40"));
+        assert_eq!(Ok(96126400.), p("3y 2w 3d 13:46:40"));
+        assert_eq!(Ok(-86401.), p("-1d 00:00:01"));
+        assert_eq!(Ok(-3888.), p("-1:04:48"));
+        assert_eq!(Err(HumanParseError::Empty), p(""));
+        assert_eq!(Err(HumanParseError::InvalidUnit), p("3"));
+        assert_eq!(Err(HumanParseError::InvalidUnit), p("3min"));
+        assert_eq!(Err(HumanParseError::InvalidNumber), p("--3s"));
+        assert_eq!(Err(HumanParseError::InvalidFormat), p("1:60"));
+        assert_eq!(Err(HumanParseError::InvalidFormat), p("1.5:00"));
+        assert_eq!(Err(HumanParseError::InvalidFormat), p("1:00:00:00"));
+        assert_eq!(Err(HumanParseError::InvalidFormat), p("1::00"));
+        assert_eq!(Err(HumanParseError::InvalidUnit), p("1x 00:00:00"));
+        assert_eq!(Ok(3888.), p("1h4m48s"));
+        assert_eq!(Ok(3888.), p("1h 4m 48s"));
+        assert_eq!(Ok(-1000000.), p("-11d13h46m40s"));
+        assert_eq!(Ok(90.5), p("1m 30s 500ms"));
+        assert_eq!(Err(HumanParseError::InvalidNumber), p("1h -4m"));
+        assert_eq!(Err(HumanParseError::InvalidUnit), p("1h 4"));
+        assert_eq!(Ok(3888.), p("1 hour, 4 minutes and 48 seconds"));
+        assert_eq!(Ok(7200.), p("about 2 hours"));
+        assert_eq!(Ok(15.6e-6), p("15.6 microseconds"));
+        assert_eq!(Ok(1.), p("1 second"));
+        assert_eq!(Ok(-180.), p("3 minutes ago"));
+        assert_eq!(Ok(7200.), p("in 2 hours"));
+        assert_eq!(Ok(0.), p("just now"));
+        assert_eq!(Ok(-86400.), p("yesterday"));
+
+        for val in [0.0000156, 0.8015, 59.99, 68.09, 1160.36, 3888.395, 1000000.] {
+            let h = val.human_duration().to_string();
+            assert_eq!(h, h.parse::<HumanDurationData>().unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn config() {
+        let c = crate::HumanConfig::new().space(true);
+        assert_eq!("15.6 µs", 0.0000156.human_duration().with_config(c));
+        assert_eq!("3.44 s", 3.435999.human_duration().with_config(c));
+        assert_eq!("1:04:48", 3888.395.human_duration().with_config(c));
+    }
+
+    #[test]
+    fn max_unit() {
+        use crate::{HumanConfig, TimeUnit};
+        let c = |max| HumanConfig::new().max_unit(max);
+        let h = |val: u64, max| val.human_duration().with_config(c(max)).to_string();
+        assert_eq!("277:46:40", h(1000000, TimeUnit::Hours));
+        assert_eq!("11d 13:46:40", h(1000000, TimeUnit::Days));
+        assert_eq!("1w 4d 13:46:40", h(1000000, TimeUnit::Weeks));
+        assert_eq!("2w 13:46:40", h(1259200, TimeUnit::Weeks));
+        assert_eq!("1w 4d 13:46:40", h(1000000, TimeUnit::Years));
+        assert_eq!("1y 00:00:05", h(31536005, TimeUnit::Years));
+        assert_eq!("3y 2w 3d 13:46:40", h(96126400, TimeUnit::Years));
+        assert_eq!("1112d 13:46:40", h(96126400, TimeUnit::Days));
+        assert_eq!("1:04:48", h(3888, TimeUnit::Years));
+    }
+
+    #[test]
+    fn compound() {
+        use crate::{DurationStyle, HumanConfig, TimeUnit};
+        let c = HumanConfig::new().duration_style(DurationStyle::Compound);
+        let h = |val: f64, c| val.human_duration().with_config(c).to_string();
+        assert_eq!("3.44s", h(3.435999, c));
+        assert_eq!("1m", h(59.995, c));
+        assert_eq!("1m8s", h(68.09, c));
+        assert_eq!("1h4m48s", h(3888.395, c));
+        assert_eq!("1h48s", h(3648., c));
+        assert_eq!("-1h4m48s", h(-3888.395, c));
+        assert_eq!("11d13h46m40s", h(1000000., c));
+        assert_eq!("1h 4m 48s", h(3888.395, c.space(true)));
+        assert_eq!("1h 5m", h(3888.395, c.space(true).max_parts(2)));
+        assert_eq!("1h", h(3888.395, c.max_parts(1)));
+        assert_eq!("1h", h(3599.6, c.max_parts(2)));
+        assert_eq!("12d", h(1000000., c.max_parts(1)));
+        assert_eq!(
+            "1w4d14h",
+            h(1000000., c.max_parts(3).max_unit(TimeUnit::Weeks))
+        );
+        assert_eq!(
+            "277h47m",
+            h(1000000., c.max_parts(2).max_unit(TimeUnit::Hours))
+        );
+    }
+
+    #[test]
+    fn long() {
+        use crate::{DurationStyle, HumanConfig};
+        let c = HumanConfig::new().duration_style(DurationStyle::Long);
+        let h = |val: f64, c| val.human_duration().with_config(c).to_string();
+        assert_eq!("1 second", h(1., c));
+        assert_eq!("1.5 seconds", h(1.5, c));
+        assert_eq!("0 nanoseconds", h(0., c));
+        assert_eq!("15.6 microseconds", h(0.0000156, c));
+        assert_eq!("1 millisecond", h(0.000999999, c));
+        assert_eq!("1 minute", h(59.995, c));
+        assert_eq!("1 minute and 8 seconds", h(68.09, c));
+        assert_eq!("1 hour, 4 minutes and 48 seconds", h(3888.395, c));
+        assert_eq!("1 hour and 48 seconds", h(3648., c));
+        assert_eq!(
+            "11 days, 13 hours, 46 minutes and 40 seconds",
+            h(1000000., c)
+        );
+        assert_eq!("about 1 hour and 5 minutes", h(3888.395, c.max_parts(2)));
+        assert_eq!("about 2 hours", h(6000., c.max_parts(1)));
+        assert_eq!("2 hours", h(7200.4, c.max_parts(1)));
+        assert_eq!("-1 hour and 2 minutes", h(-3720., c));
+    }
+
+    #[test]
+    fn relative() {
+        use crate::HumanConfig;
+        use std::time::Duration;
+        assert_eq!("just now", 0.human_relative());
+        assert_eq!("just now", (-9.9).human_relative());
+        assert_eq!("10 seconds ago", (-10).human_relative());
+        assert_eq!("in 45 seconds", 45.human_relative());
+        assert_eq!("1 minute ago", (-59.6).human_relative());
+        assert_eq!("3 minutes ago", (-190).human_relative());
+        assert_eq!("in 1 hour", 3888.human_relative());
+        assert_eq!("in 2 hours", 6000.human_relative());
+        assert_eq!("23 hours ago", (-23. * 3600.).human_relative());
+        assert_eq!("yesterday", (-86400).human_relative());
+        assert_eq!("yesterday", (-86399).human_relative());
+        assert_eq!("yesterday", (-23.5 * 3600.).human_relative());
+        assert_eq!("22 hours ago", (-22.4 * 3600.).human_relative());
+        assert_eq!("tomorrow", 86399.human_relative());
+        assert_eq!("tomorrow", (35. * 3600.).human_relative());
+        assert_eq!("in 2 days", (47. * 3600.).human_relative());
+        assert_eq!("2 days ago", (-47.99 * 3600.).human_relative());
+        assert_eq!("2 days ago", (-2 * 86400).human_relative());
+        assert_eq!("in 12 days", 1000000.human_relative());
+        assert_eq!(
+            "in 1 second",
+            Duration::from_secs(1).human_relative().with_config(
+                HumanConfig::new()
+                    .duration_style(crate::DurationStyle::Relative)
+                    .just_now(Duration::ZERO)
+            )
+        );
+
+        let c = HumanConfig::new()
+            .duration_style(crate::DurationStyle::Relative)
+            .just_now(Duration::from_secs(60))
+            .yesterday(Duration::ZERO);
+        assert_eq!("just now", (-59).human_duration().with_config(c));
+        assert_eq!("1 day ago", (-86400).human_duration().with_config(c));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn relative_times() {
+        use crate::HumanDurationData;
+        use std::time::{Duration, SystemTime};
+        let now = SystemTime::now();
+        let h = |secs: i64| match secs < 0 {
+            true => {
+                HumanDurationData::relative(now - Duration::from_secs(secs.unsigned_abs()), now)
+            }
+            false => HumanDurationData::relative(now + Duration::from_secs(secs as u64), now),
+        };
+        assert_eq!("5 minutes ago", h(-300));
+        assert_eq!("in 5 minutes", h(300));
+    }
+
+    #[test]
+    fn non_finite() {
+        use crate::{DurationStyle, HumanConfig};
+        assert_eq!("NaN", f64::NAN.human_duration());
+        assert_eq!("∞", f64::INFINITY.human_duration());
+        assert_eq!("-∞", f64::NEG_INFINITY.human_duration());
+        assert_eq!("0ns", (-0.).human_duration());
+        assert_eq!("0ns", (-1e-12).human_duration());
+        assert_eq!("just now", (-0.).human_relative());
+        let c = HumanConfig::new()
+            .duration_style(DurationStyle::Long)
+            .nan("unknown")
+            .infinity("forever");
+        assert_eq!("unknown", f64::NAN.human_duration().with_config(c));
+        assert_eq!("forever", f64::INFINITY.human_duration().with_config(c));
+    }
+
+    #[test]
+    fn render() {
+        use crate::{DurationStyle, HumanConfig, HumanDurationData, HumanStr, TimeUnit};
+        let s: HumanStr<{ HumanDurationData::MAX_LEN }> = 3888.human_duration().render().unwrap();
+        assert_eq!("1:04:48", s);
+        assert!(3888.human_duration().render::<6>().is_none());
+        let mut buf = [0; 8];
+        assert_eq!(7, 3888.human_duration().write_to(&mut buf));
+        assert_eq!(b"1:04:48", &buf[..7]);
+
+        let worst = HumanConfig::new()
+            .duration_style(DurationStyle::Long)
+            .max_unit(TimeUnit::Years);
+        let h = (-1.7976931348623153e308)
+            .human_duration()
+            .with_config(worst);
+        assert_eq!(HumanDurationData::MAX_LEN, h.to_string().len());
+        assert!(h.render::<{ HumanDurationData::MAX_LEN }>().is_some());
+    }
+
+    #[test]
+    fn padding() {
+        use crate::{DurationStyle, HumanConfig};
+        assert_eq!("  15.6µs", format!("{:>8}", 0.0000156.human_duration()));
+        assert_eq!(" 1:04:48 ", format!("{:^9}", 3888.human_duration()));
+        assert_eq!("+1:04:48", format!("{:+}", 3888.human_duration()));
+        assert_eq!("-1:04:48", format!("{:+}", (-3888).human_duration()));
+        let long = HumanConfig::new()
+            .duration_style(DurationStyle::Long)
+            .max_parts(1);
+        assert_eq!(
+            "about 1 hour..",
+            format!("{:.<14}", 3888.human_duration().with_config(long))
+        );
+        assert_eq!("in 1 hour", format!("{:+}", 3888.human_relative()));
+    }
+
+    #[test]
+    fn fixed_width() {
+        use crate::{DurationStyle, HumanConfig, Precision};
+        let c = HumanConfig::new().fixed_width(true);
+        let h = |val: f64| val.human_duration().with_config(c);
+        assert_eq!(" 15.60µs", h(0.0000156));
+        assert_eq!("   5.00s", h(5.));
+        assert_eq!("  1:00.0", h(60.));
+        assert_eq!(" 1:04:48", h(3888.));
+        assert_eq!("23:59:59", h(86399.));
+        assert_eq!("1d 00:00:00", h(86400.));
+        let d3 = c.precision(Precision::Decimals(3));
+        assert_eq!("      5.000s", 5.human_duration().with_config(d3));
+        assert_eq!(
+            " 1:00.000",
+            format!("{:>9}", 60.human_duration().with_config(d3))
+        );
+        assert_eq!("    1:00.000", 60.human_duration().with_config(d3));
+        assert_eq!(" 1:04:48.000", 3888.human_duration().with_config(d3));
+        let long = c.duration_style(DurationStyle::Long);
+        assert_eq!("1 minute", 60.human_duration().with_config(long));
+    }
+
+    #[test]
+    fn precision() {
+        use crate::{DurationStyle, HumanConfig, Precision};
+        let s3 = HumanConfig::new().precision(Precision::Significant(3));
+        let d2 = HumanConfig::new().precision(Precision::Decimals(2));
+        assert_eq!("15.6µs", 0.0000156.human_duration().with_config(s3));
+        assert_eq!("1.00s", 1.human_duration().with_config(s3));
+        assert_eq!("15.60µs", 0.0000156.human_duration().with_config(d2));
+        assert_eq!("1:08.1", 68.09.human_duration().with_config(s3));
+        assert_eq!("1:08.09", 68.09.human_duration().with_config(d2));
+        assert_eq!("1:04:48.40", 3888.395.human_duration().with_config(d2));
+        assert_eq!("1d 03:46:40.00", 100000.human_duration().with_config(d2));
+        assert_eq!("1:00.00", 59.9999.human_duration().with_config(d2));
+        assert_eq!("3.436s", format!("{:.3}", 3.435999.human_duration()));
+        assert_eq!("1:04:48.395", format!("{:.3}", 3888.395.human_duration()));
+        assert_eq!("1:05", format!("{:.0}", 64.5.human_duration()));
+        let long = d2.duration_style(DurationStyle::Long);
+        assert_eq!("1.00 seconds", 1.human_duration().with_config(long));
+        assert_eq!(
+            "1 second",
+            format!("{:.0}", 1.human_duration().with_config(long))
+        );
+        assert_eq!(
+            "1 minute and 8 seconds",
+            68.09.human_duration().with_config(long)
+        );
+    }
+
+    #[test]
+    fn exact() {
+        use crate::{DurationStyle, HumanConfig};
+        use std::time::Duration;
+        let iso = HumanConfig::new().duration_style(DurationStyle::Iso8601);
+        let d = Duration::new(10_000_000_000, 1);
+        assert_eq!(
+            "P115740DT17H46M40.000000001S",
+            d.human_duration().with_config(iso)
+        );
+        assert_eq!(
+            "PT0.000000001S",
+            Duration::new(0, 1).human_duration().with_config(iso)
+        );
+        assert_eq!("1.1ms", Duration::new(0, 1_050_000).human_duration());
+        assert_eq!("-1d 00:00:01", (-86401_i64).human_duration());
+        assert_eq!("11d 13:46:40", 1_000_000_u32.human_duration());
+        let max = Duration::new(u64::MAX, 999_999_999);
+        assert_eq!(Ok(max), Duration::try_from(max.human_duration()));
+    }
+
+    #[test]
+    fn beyond_nanos() {
+        use crate::{DurationStyle, HumanConfig, HumanDurationData, TimeUnit};
+        assert_eq!(
+            "11574074074074074864156672d 21:24:16",
+            1e30.human_duration()
+        );
+        let h = 1e300.human_duration().to_string();
+        assert!(
+            h.starts_with("1157407407407407528683678754486667882174598")
+                && h.ends_with("d 17:36:00")
+        );
+        let p = |s: &str| HumanDurationData::parse(s).unwrap().value();
+        assert_eq!(1e30, p("11574074074074074864156672d 21:24:16"));
+        assert_eq!(1e300, p(&h));
+
+        let c = HumanConfig::new().max_unit(TimeUnit::Hours);
+        assert_eq!(
+            "-277777777777777796739760128:24:16",
+            (-1e30).human_duration().with_config(c)
+        );
+        let c = c.duration_style(DurationStyle::Compound);
+        assert_eq!(
+            "277777777777777796739760128h24m16s",
+            1e30.human_duration().with_config(c)
+        );
+        let c = HumanConfig::new().max_unit(TimeUnit::Years);
+        let long = c.duration_style(DurationStyle::Long).max_parts(2);
+        assert_eq!(
+            "about 31709791983764588462080 years and 44 weeks",
+            1e30.human_duration().with_config(long)
+        );
+        let relative = c.duration_style(DurationStyle::Relative);
+        assert_eq!(
+            "31709791983764588462080 years ago",
+            (-1e30).human_duration().with_config(relative)
+        );
+        let iso = c.duration_style(DurationStyle::Iso8601);
+        assert_eq!(
+            "P11574074074074074864156672DT21H24M16S",
+            1e30.human_duration().with_config(iso)
+        );
+    }
+
+    #[test]
+    fn iso8601() {
+        use crate::{DurationStyle, HumanConfig, HumanDurationData, HumanParseError, TimeUnit};
+        let c = HumanConfig::new().duration_style(DurationStyle::Iso8601);
+        let h = |val: f64, c| val.human_duration().with_config(c).to_string();
+        assert_eq!("PT0S", h(0., c));
+        assert_eq!("PT1S", h(1., c));
+        assert_eq!("PT0.0000156S", h(0.0000156, c));
+        assert_eq!("PT0.000000001S", h(1e-9, c));
+        assert_eq!("PT3.435999S", h(3.435999, c));
+        assert_eq!("PT1M", h(60., c));
+        assert_eq!("PT1H4M48.395S", h(3888.395, c));
+        assert_eq!("PT1H48S", h(3648., c));
+        assert_eq!("-PT1H4M48.395S", h(-3888.395, c));
+        assert_eq!("P1D", h(86400., c));
+        assert_eq!("P11DT13H46M40S", h(1000000., c));
+        assert_eq!("PT277H46M40S", h(1000000., c.max_unit(TimeUnit::Hours)));
+
+        let p = |s: &str| HumanDurationData::parse_iso8601(s).map(|h| h.val);
+        assert_eq!(Ok(3888.395), p("PT1H4M48.395S"));
+        assert_eq!(Ok(1000000.), p("P11DT13H46M40S"));
+        assert_eq!(Ok(1000000.), p("PT277H46M40S"));
+        assert_eq!(Ok(-0.5), p("-PT0.5S"));
+        assert_eq!(Ok(1209600.), p("P2W"));
+        assert_eq!(Ok(90.), p("PT1.5M"));
+        assert_eq!(Ok(0.), p("PT0S"));
+        assert_eq!(Err(HumanParseError::Empty), p(""));
+        assert_eq!(Err(HumanParseError::InvalidFormat), p("1H"));
+        assert_eq!(Err(HumanParseError::InvalidFormat), p("P"));
+        assert_eq!(Err(HumanParseError::InvalidFormat), p("PT"));
+        assert_eq!(Err(HumanParseError::InvalidFormat), p("PT1H 2M"));
+        assert_eq!(Err(HumanParseError::InvalidFormat), p("PT1"));
+        assert_eq!(Err(HumanParseError::InvalidUnit), p("P1Y"));
+        assert_eq!(Err(HumanParseError::InvalidUnit), p("PT1S2M"));
+        assert_eq!(Err(HumanParseError::InvalidUnit), p("P1H"));
+
+        for val in [0.0000156, 3.435999, 3888.395, 1000000., -42.5] {
+            assert_eq!(Ok(val), p(&h(val, c)));
+            assert_eq!(Ok(val), HumanDurationData::parse(&h(val, c)).map(|h| h.val));
+        }
+        let p = |s: &str| HumanDurationData::parse(s).map(|h| h.val);
+        assert_eq!((Ok(86400.), Ok(-0.5)), (p("P1D"), p(" -PT0.5S")));
+        assert_eq!(Err(HumanParseError::InvalidUnit), p("P1Y"));
+    }
 }
 
 #[test]
@@ -176,5 +1316,9 @@ fn serialize() -> Result<(), serde_json::Error> {
     assert_eq!(r#"{"val":123456.0}"#, &ser);
     let h2 = serde_json::from_str::<HumanDurationData>(&ser)?;
     assert_eq!(h, h2);
+    let h = std::time::Duration::from_secs((1 << 53) - 3).human_duration();
+    let h2 = serde_json::from_str::<HumanDurationData>(&serde_json::to_string(&h)?)?;
+    let expected = "104249991374d 07:36:29";
+    assert_eq!((expected, h.to_string()), (expected, h2.to_string()));
     Ok(())
 }
@@ -0,0 +1,84 @@
+use crate::utils::SliceWriter;
+use core::fmt::{self, Write};
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+/// A human representation rendered inline in up to `N` bytes, which derefs to a `str`, so it
+/// can be stored and passed around without allocating.
+///
+/// Use the `MAX_LEN` of each data type to size it, plus the length of the unit, like
+/// [`HumanCountData::MAX_LEN`](crate::HumanCountData::MAX_LEN).
+#[derive(Clone, Copy)]
+pub struct HumanStr<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> HumanStr<N> {
+    /// Render a human representation, if it fits.
+    pub(crate) fn render(human: &impl fmt::Display) -> Option<Self> {
+        let mut buf = [0; N];
+        let mut w = SliceWriter::new(&mut buf);
+        write!(w, "{}", human).ok()?;
+        let len = w.as_str().len();
+        Some(HumanStr { buf, len })
+    }
+
+    /// The rendered representation.
+    pub fn as_str(&self) -> &str {
+        // only whole `str`s are ever written.
+        core::str::from_utf8(&self.buf[..self.len]).unwrap()
+    }
+}
+
+impl<const N: usize> Deref for HumanStr<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> AsRef<str> for HumanStr<N> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> fmt::Display for HumanStr<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl<const N: usize> fmt::Debug for HumanStr<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> PartialEq for HumanStr<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<const N: usize> Eq for HumanStr<N> {}
+
+impl<const N: usize> Hash for HumanStr<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for HumanStr<N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const N: usize> PartialEq<HumanStr<N>> for &str {
+    fn eq(&self, other: &HumanStr<N>) -> bool {
+        *self == other.as_str()
+    }
+}
@@ -1,6 +1,17 @@
-use super::HumanThroughputData;
-use crate::utils::{self, SPACE};
-use std::fmt::{self, Debug, Display};
+use super::{
+    BitNotation, HumanConfig, HumanCountData, HumanDurationData, HumanParseError, HumanStr,
+    HumanThroughputData, TimePerItem, BITS,
+};
+use crate::human_duration::NANOS;
+use crate::utils::Exact;
+use crate::Unit;
+use crate::{human_count, human_duration, parse, utils};
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Display};
+use core::hash::{Hash, Hasher};
+#[cfg(feature = "alloc")]
+use core::str::FromStr;
+use core::time::Duration;
 
 const SPEC: &[(f64, &str, usize)] = &[
     (24., "/d", 2),
@@ -9,24 +20,351 @@ const SPEC: &[(f64, &str, usize)] = &[
     // "/s" in code.
 ];
 
+impl<'a> HumanThroughputData<'a> {
+    /// The maximum length in bytes of any throughput, besides its unit, as long as the NaN and
+    /// infinity strings are the default ones. It is that large due to times per item, which
+    /// can be long durations.
+    pub const MAX_LEN: usize = HumanDurationData::MAX_LEN + "/it".len();
+
+    /// Create a throughput of a raw value in units per second.
+    #[cfg_attr(
+        not(any(feature = "1024", feature = "iec", feature = "space")),
+        doc = r#"
+
+```
+use human_repr::HumanThroughputData;
+assert_eq!("30req/min", HumanThroughputData::new(0.5, "req"));
+```
+"#
+    )]
+    pub fn new(val: f64, unit: impl Into<Unit<'a>>) -> Self {
+        HumanThroughputData {
+            val,
+            unit: unit.into(),
+            exact: None,
+            empty_sum: false,
+            config: HumanConfig::new(),
+        }
+    }
+
+    /// Render this throughput with a custom [`HumanConfig`], instead of the default one.
+    pub fn with_config(mut self, config: HumanConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Render this throughput inline, without allocating, if it fits in `N` bytes.
+    pub fn render<const N: usize>(&self) -> Option<HumanStr<N>> {
+        HumanStr::render(self)
+    }
+
+    /// Render this throughput into a buffer, returning the number of bytes written, which is
+    /// zero when it doesn't fit.
+    pub fn write_to(&self, buf: &mut [u8]) -> usize {
+        utils::write_to(buf, self)
+    }
+
+    /// The raw value of this throughput, in units per second.
+    pub fn value(&self) -> f64 {
+        self.val
+    }
+
+    /// The unit of this throughput.
+    // the unit is already a `&str` without alloc, but a `Cow` to borrow from with it.
+    #[cfg_attr(not(feature = "alloc"), allow(clippy::needless_borrow))]
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    /// The [`HumanConfig`] this throughput is rendered with.
+    pub fn config(&self) -> HumanConfig {
+        self.config
+    }
+
+    /// Convert this throughput into one that owns its unit, so it can outlive the borrowed one.
+    #[cfg(feature = "alloc")]
+    pub fn into_owned(self) -> HumanThroughputData<'static> {
+        HumanThroughputData {
+            val: self.val,
+            unit: Unit::Owned(self.unit.into_owned()),
+            exact: self.exact,
+            empty_sum: self.empty_sum,
+            config: self.config,
+        }
+    }
+
+    /// Convert this throughput of bytes into bits, like [`human_throughput_bits`](crate::HumanThroughput::human_throughput_bits)
+    /// would generate.
+    #[cfg_attr(
+        not(any(feature = "1024", feature = "iec", feature = "space")),
+        doc = r#"
+
+```
+use human_repr::HumanThroughput;
+assert_eq!("9.9Mbit/s", 1234567.human_throughput_bytes().into_bits());
+```
+"#
+    )]
+    pub fn into_bits(self) -> HumanThroughputData<'static> {
+        HumanThroughputData {
+            val: self.val * 8.,
+            unit: Unit::from(BITS),
+            exact: self.exact.and_then(|e| e.checked_mul(8)),
+            empty_sum: false,
+            config: self.config,
+        }
+    }
+
+    /// Create a throughput of a count over an elapsed duration, with integer math that keeps the
+    /// full precision of the count, and is exact when the rate is a whole number.
+    ///
+    /// A zero duration never divides by zero: it makes a zero rate for a zero count, and an
+    /// infinite one otherwise, which is rendered like `∞B/s`.
+    #[cfg_attr(
+        not(any(feature = "1024", feature = "iec", feature = "space")),
+        doc = r#"
+
+```
+use human_repr::HumanThroughputData;
+use std::time::Duration;
+
+let h = HumanThroughputData::from_count_and_duration(3431237, Duration::from_millis(1500), "B");
+assert_eq!("2.3MB/s", h);
+let h = HumanThroughputData::from_count_and_duration(5, Duration::ZERO, "files");
+assert_eq!("∞files/s", h);
+```
+"#
+    )]
+    pub fn from_count_and_duration(
+        count: u64,
+        elapsed: Duration,
+        unit: impl Into<Unit<'a>>,
+    ) -> Self {
+        let (num, nanos) = (count as u128 * NANOS, elapsed.as_nanos());
+        let (val, exact) = match (num, nanos) {
+            (0, _) => (0., Some(Exact::new(false, 0))),
+            (_, 0) => (f64::INFINITY, None),
+            _ => {
+                let (q, r) = (num / nanos, num % nanos);
+                let exact = (r == 0).then(|| Exact::new(false, q));
+                (q as f64 + r as f64 / nanos as f64, exact)
+            }
+        };
+        HumanThroughputData {
+            val,
+            unit: unit.into(),
+            exact,
+            empty_sum: false,
+            config: HumanConfig::new(),
+        }
+    }
+
+    /// Parse a human throughput like `"1.2MB/s"` or `"6.1tests/min"` back into its value,
+    /// normalized to units per second, and unit.
+    ///
+    /// Prefixes are understood only in the per second format, just like the Display impl
+    /// generates them, see [`HumanCountData::parse`] for the details.
+    /// Bits are also understood in the `bps` notation, like `"1.2Mbps"` or `"30b/min"`, as well as
+    /// times per item in any duration format, like `"4:10/file"` or `"2.3s/it"`, where `it` is
+    /// the bare unit.
+    #[cfg_attr(
+        not(any(feature = "1024", feature = "iec", feature = "space")),
+        doc = r#"
+
+```
+use human_repr::{HumanThroughput, HumanThroughputData};
+let h = HumanThroughputData::parse("30req/min")?;
+assert_eq!(0.5.human_throughput("req"), h);
+# Ok::<(), human_repr::HumanParseError>(())
+```
+"#
+    )]
+    pub fn parse(s: &'a str) -> Result<Self, HumanParseError> {
+        Self::parse_with(s, HumanConfig::new())
+    }
+
+    /// Parse a human throughput like [`parse`](Self::parse), but with a custom [`HumanConfig`],
+    /// which determines the SI prefixes divisor, and is also set in the result.
+    pub fn parse_with(s: &'a str, config: HumanConfig) -> Result<Self, HumanParseError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(HumanParseError::Empty);
+        }
+
+        if let Some(count) = s.strip_suffix("bps") {
+            let h = HumanCountData::parse_with(count, config)?;
+            if !h.unit.is_empty() {
+                return Err(HumanParseError::InvalidUnit);
+            }
+            return Ok(HumanThroughputData {
+                val: h.val,
+                unit: Unit::from(BITS),
+                exact: None,
+                empty_sum: false,
+                config,
+            });
+        }
+
+        let (count, scale) = s.rsplit_once('/').ok_or(HumanParseError::InvalidUnit)?;
+        if scale == "s" {
+            let HumanCountData { val, unit, .. } = HumanCountData::parse_with(count, config)?;
+            return Ok(HumanThroughputData {
+                val,
+                unit,
+                exact: None,
+                empty_sum: false,
+                config,
+            });
+        }
+
+        let mut secs = 60. * 60. * 24.;
+        for &(size, spec_scale, _) in SPEC {
+            if spec_scale[1..] == *scale {
+                let (num, unit) = parse::split_number(count)?;
+                return Ok(HumanThroughputData {
+                    val: parse::parse_scaled(num, 0) / secs,
+                    unit: Unit::from(human_count::parsed_unit(unit)),
+                    exact: None,
+                    empty_sum: false,
+                    config,
+                });
+            }
+            secs /= size;
+        }
+
+        // the time per item, like "2.3s/it" or "4:10/file".
+        let per_item = HumanDurationData::parse(count).map_err(|_| HumanParseError::InvalidUnit)?;
+        Ok(HumanThroughputData {
+            val: 1. / per_item.val,
+            unit: Unit::from(parsed_item(scale)),
+            exact: None,
+            empty_sum: false,
+            config,
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromStr for HumanThroughputData<'static> {
+    type Err = HumanParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let h = HumanThroughputData::parse(s)?;
+        Ok(HumanThroughputData {
+            unit: Unit::Owned(h.unit.into_owned()),
+            ..h
+        })
+    }
+}
+
 impl Display for HumanThroughputData<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let HumanThroughputData { mut val, unit } = self;
-        val *= 60. * 60. * 24.;
-        for &(size, scale, dec) in SPEC {
-            match utils::rounded(val, dec) {
-                r if r.abs() >= size => val /= size,
-                r if r.fract() == 0. => return write!(f, "{:.0}{}{}{}", r, SPACE, unit, scale),
-                r if (r * 10.).fract() == 0. => {
-                    return write!(f, "{:.1}{}{}{}", r, SPACE, unit, scale)
-                }
-                r => return write!(f, "{:.2}{}{}{}", r, SPACE, unit, scale),
+        let config = self.config.with_formatter(f);
+        let width = fixed_width(self.unit(), &config);
+        utils::pad(f, width, |f| fmt_throughput(f, self, &config))
+    }
+}
+
+/// The suffix of throughputs per second, where bits follow the config notation.
+fn per_second(unit: &str, config: &HumanConfig) -> &'static str {
+    match (unit, config.bit_notation) {
+        (BITS, BitNotation::Bps) => "ps",
+        _ => "/s",
+    }
+}
+
+/// The unit of times per item, where a bare unit becomes `it`.
+fn item<'u>(unit: &'u str, config: &HumanConfig) -> &'u str {
+    match human_count::rendered_unit(unit, config) {
+        "" => "it",
+        unit => unit,
+    }
+}
+
+/// The unit of a parsed time per item, where `it` becomes the bare unit again.
+fn parsed_item(item: &str) -> &str {
+    match item {
+        "it" => "",
+        unit => human_count::parsed_unit(unit),
+    }
+}
+
+/// The width of any throughput with this unit in the fixed-width mode, per second or in the
+/// larger scales, or of times per item in the clock style.
+fn fixed_width(unit: &str, config: &HumanConfig) -> Option<usize> {
+    let per_item =
+        || Some(human_duration::fixed_width(config)? + 1 + item(unit, config).chars().count());
+    match config.time_per_item {
+        TimePerItem::Never => per_time(unit, config),
+        TimePerItem::Auto => Some(per_time(unit, config)?.max(per_item()?)),
+        TimePerItem::Always => per_item(),
+    }
+}
+
+/// The width of any throughput per time, per second or in the larger scales.
+fn per_time(unit: &str, config: &HumanConfig) -> Option<usize> {
+    let count = human_count::fixed_width(unit, config)? + per_second(unit, config).len();
+    let scale = SPEC.iter().map(|&(_, s, _)| s.len()).max().unwrap_or(0);
+    let scaled = utils::num_width(config.scaled_precision(), 2, true)
+        + config.separator().len()
+        + human_count::rendered_unit(unit, config).chars().count()
+        + scale;
+    Some(count.max(scaled))
+}
+
+/// Render a throughput, with a config that already includes the format string options.
+fn fmt_throughput(
+    f: &mut dyn fmt::Write,
+    h: &HumanThroughputData<'_>,
+    config: &HumanConfig,
+) -> fmt::Result {
+    let HumanThroughputData { unit, exact, .. } = h;
+    let val = h.val;
+    let unit: &str = unit;
+    let (space, precision) = (config.separator(), config.scaled_precision());
+    let (shown, per) = (
+        human_count::rendered_unit(unit, config),
+        per_second(unit, config),
+    );
+    if !val.is_finite() {
+        return utils::fmt_non_finite(f, val, config, format_args!("{}{}{}", space, shown, per));
+    }
+    let per_item = match config.time_per_item {
+        TimePerItem::Never => false,
+        TimePerItem::Auto => val != 0. && val.abs() < 1.,
+        TimePerItem::Always => true,
+    };
+    if per_item {
+        let secs = if val == 0. { f64::INFINITY } else { 1. / val };
+        let per_item = HumanDurationData {
+            val: secs,
+            exact: None,
+            config: *config,
+        };
+        human_duration::fmt_duration(f, &per_item, config)?;
+        return write!(f, "/{}", item(unit, config));
+    }
+    let mut secs = 60. * 60. * 24.;
+    for &(size, scale, dec) in SPEC {
+        // huge values would overflow in the larger scales, so they are only scaled when below them.
+        if val.abs() < size / secs {
+            let r = utils::fixed(val * secs, precision, dec);
+            if !r.at_least(size as u128) {
+                return write!(f, "{}{}{}{}", r, space, shown, scale);
             }
         }
-
-        use super::HumanCount;
-        write!(f, "{}/s", val.human_count(unit.as_ref()))
+        secs /= size;
     }
+
+    let count = HumanCountData {
+        val,
+        unit: Unit::from(unit),
+        exact: *exact,
+        empty_sum: false,
+        config: *config,
+    };
+    human_count::fmt_count(f, &count, config)?;
+    f.write_str(per)
 }
 
 impl Debug for HumanThroughputData<'_> {
@@ -40,6 +378,47 @@ impl Debug for HumanThroughputData<'_> {
     }
 }
 
+impl HumanThroughputData<'_> {
+    /// The exact integer of this throughput, which whole values also have, so they render and
+    /// compare alike however they were created.
+    fn exact_or_whole(&self) -> Option<Exact> {
+        self.exact.or_else(|| Exact::from_whole(self.val))
+    }
+
+    fn key(&self) -> utils::Key {
+        utils::Key {
+            val: self.val,
+            exact: self.exact_or_whole(),
+        }
+    }
+}
+
+/// Exact values compare exactly, just like they render, and all NaN values are equal, so it is
+/// also an `Eq` and `Hash` key.
+impl PartialEq for HumanThroughputData<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key() && self.unit == other.unit
+    }
+}
+
+impl Eq for HumanThroughputData<'_> {}
+
+impl Hash for HumanThroughputData<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+        self.unit.hash(state);
+    }
+}
+
+impl PartialOrd for HumanThroughputData<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.key().partial_cmp(&other.key()) {
+            Some(Ordering::Equal) => self.unit().partial_cmp(other.unit()),
+            ord => ord,
+        }
+    }
+}
+
 impl PartialEq<HumanThroughputData<'_>> for &str {
     fn eq(&self, other: &HumanThroughputData<'_>) -> bool {
         utils::display_compare(self, other)
@@ -53,6 +432,7 @@ impl PartialEq<&str> for HumanThroughputData<'_> {
 }
 
 #[cfg(all(test, not(any(feature = "1024", feature = "iec", feature = "space"))))]
+#[allow(clippy::needless_borrows_for_generic_args)] // the flexibility tests predate this lint.
 mod tests {
     use crate::HumanThroughput;
 
@@ -91,7 +471,9 @@ mod tests {
     #[test]
     fn flexibility() {
         assert_eq!("123MCrabs/s", 123e6.human_throughput("Crabs"));
+        #[cfg(feature = "alloc")]
         assert_eq!("123MCrabs/s", 123e6.human_throughput("Crabs".to_owned()));
+        #[cfg(feature = "alloc")]
         assert_eq!("123MCrabs/s", 123e6.human_throughput(&"Crabs".to_owned()));
         assert_eq!("123M🦀/s", 123e6.human_throughput("🦀"));
         assert_eq!("12.3k°C/s", 123e2.human_throughput("°C"));
@@ -111,6 +493,236 @@ mod tests {
     fn symmetric() {
         assert_eq!(1.human_throughput_bytes(), "1B/s");
     }
+
+    #[test]
+    fn value() {
+        use crate::{HumanConfig, HumanThroughputData};
+        use std::collections::HashSet;
+        let h = HumanThroughputData::new(-0., "B");
+        assert_eq!(h, 0.human_throughput_bytes());
+        let nan = f64::NAN.human_throughput_bytes();
+        assert_eq!(nan, (-f64::NAN).human_throughput_bytes());
+        let set = HashSet::from([h, 0.human_throughput_bytes(), nan.clone(), nan]);
+        assert_eq!(2, set.len());
+        let (a, b) = ((1_u64 << 60) + 1, (1_u64 << 60) + 2);
+        let (ha, hb) = (a.human_throughput_bytes(), b.human_throughput_bytes());
+        assert!(ha != hb && ha < hb);
+        assert_eq!(2, HashSet::from([ha, hb]).len());
+        let h = 123456
+            .human_throughput("it")
+            .with_config(HumanConfig::new().space(true));
+        assert_eq!(HumanConfig::new().space(true), h.config());
+        assert_eq!((123456., "it"), (h.value(), h.unit()));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn into_owned() {
+        let h = {
+            let unit = String::from("files");
+            123456.human_throughput(unit.as_str()).into_owned()
+        };
+        assert_eq!("123.5kfiles/s", h);
+    }
+
+    #[test]
+    fn config() {
+        use crate::{HumanConfig, Prefixes};
+        let c = HumanConfig::new().prefixes(Prefixes::Iec).space(true);
+        assert_eq!("1.2 MiB/s", 1234567.human_throughput_bytes().with_config(c));
+        assert_eq!("30 B/min", 0.5.human_throughput_bytes().with_config(c));
+        assert_eq!("1.2MB/s", 1234567.human_throughput_bytes());
+    }
+
+    #[test]
+    fn non_finite() {
+        use crate::HumanConfig;
+        assert_eq!("NaN", f64::NAN.human_throughput_bytes());
+        assert_eq!("∞B/s", (1. / 0.).human_throughput_bytes());
+        assert_eq!("-∞B/s", f64::NEG_INFINITY.human_throughput_bytes());
+        assert_eq!("1e304B/s", 1e304.human_throughput_bytes());
+        assert_eq!("-1.8e308B/s", f64::MIN.human_throughput_bytes());
+        assert_eq!("0B/d", (-0.).human_throughput_bytes());
+        let c = HumanConfig::new().nan("—");
+        assert_eq!("—", f64::NAN.human_throughput("req").with_config(c));
+    }
+
+    #[test]
+    fn render() {
+        use crate::{DurationStyle, HumanConfig, HumanThroughputData, TimePerItem, TimeUnit};
+        let h = 1234567.human_throughput_bytes();
+        assert_eq!("1.2MB/s", h.render::<7>().unwrap());
+        assert!(h.render::<6>().is_none());
+        let mut buf = [0; HumanThroughputData::MAX_LEN];
+        assert_eq!(7, h.write_to(&mut buf));
+
+        let worst = HumanConfig::new()
+            .time_per_item(TimePerItem::Always)
+            .duration_style(DurationStyle::Long)
+            .max_unit(TimeUnit::Years);
+        let h = (-5.562684646268033e-309)
+            .human_throughput_bare()
+            .with_config(worst);
+        assert_eq!(HumanThroughputData::MAX_LEN, h.write_to(&mut buf));
+    }
+
+    #[test]
+    fn padding() {
+        assert_eq!(
+            "  1.2MB/s",
+            format!("{:>9}", 1234567.human_throughput_bytes())
+        );
+        assert_eq!("30B/min  ", format!("{:9}", 0.5.human_throughput_bytes()));
+        assert_eq!("+30B/min", format!("{:+}", 0.5.human_throughput_bytes()));
+        assert_eq!(
+            "*1.23MB/s*",
+            format!("{:*^10.2}", 1234567.human_throughput_bytes())
+        );
+    }
+
+    #[test]
+    fn bits() {
+        use crate::{BitNotation, HumanConfig, HumanThroughputData};
+        let bps = HumanConfig::new().bit_notation(BitNotation::Bps);
+        assert_eq!("1.2Mbit/s", 1234567.human_throughput_bits());
+        assert_eq!("1.2Mbps", 1234567.human_throughput_bits().with_config(bps));
+        assert_eq!("9.9Mbit/s", 1234567.human_throughput_bytes().into_bits());
+        assert_eq!("30b/min", 0.5.human_throughput_bits().with_config(bps));
+        assert_eq!(
+            "∞bps",
+            f64::INFINITY.human_throughput_bits().with_config(bps)
+        );
+        let live = bps.fixed_width(true);
+        assert_eq!(
+            "  1.23Mbps",
+            1234567.human_throughput_bits().with_config(live)
+        );
+        assert_eq!("30.00b/min", 0.5.human_throughput_bits().with_config(live));
+
+        let h = HumanThroughputData::parse("1.2Mbps").unwrap();
+        assert_eq!((1.2e6, "bit"), (h.val, h.unit()));
+        assert_eq!(HumanThroughputData::parse("1.2Mbit/s"), Ok(h));
+        assert_eq!(2., HumanThroughputData::parse("2 bps").unwrap().val);
+        assert!(HumanThroughputData::parse("1.2Mxbps").is_err());
+        let h = HumanThroughputData::parse("30b/min").unwrap();
+        assert_eq!((0.5, "bit"), (h.val, h.unit()));
+        assert_eq!(HumanThroughputData::parse("0.5b/s"), Ok(h.clone()));
+        assert_eq!("30b/min", h.with_config(bps));
+    }
+
+    #[test]
+    fn from_count_and_duration() {
+        use crate::HumanThroughputData;
+        use std::time::Duration;
+        let h = |count, nanos| {
+            HumanThroughputData::from_count_and_duration(count, Duration::from_nanos(nanos), "B")
+        };
+        assert_eq!("2.3MB/s", h(3431237, 1_500_000_000));
+        assert_eq!("1GB/s", h(1, 1));
+        assert_eq!("18.45EB/s", h(u64::MAX, 1_000_000_000));
+        assert_eq!(
+            "18.446744074EB/s",
+            format!("{:.9}", h(u64::MAX, 1_000_000_000))
+        );
+        assert_eq!((u64::MAX / 3) as f64, h(u64::MAX, 3_000_000_000).val);
+        assert_eq!("30B/min", h(1, 2_000_000_000));
+        assert_eq!("∞B/s", h(5, 0));
+        assert_eq!("0B/d", h(0, 0));
+        assert_eq!(0., h(0, 0).val);
+        assert_eq!("1GB/s", h(u64::MAX, u64::MAX));
+    }
+
+    #[test]
+    fn time_per_item() {
+        use crate::{HumanConfig, HumanThroughputData, TimePerItem};
+        let auto = HumanConfig::new().time_per_item(TimePerItem::Auto);
+        let h = |val: f64, unit: &'static str| val.human_throughput(unit).with_config(auto);
+        assert_eq!("2.3s/it", h(1. / 2.3, ""));
+        assert_eq!("4:10/file", h(1. / 250., "file"));
+        assert_eq!("1:00:00/B", h(1. / 3600., "B"));
+        assert_eq!("-2s/it", h(-0.5, ""));
+        assert_eq!("1/s", h(1., ""));
+        assert_eq!("1.2kB/s", h(1234., "B"));
+        assert_eq!("0B/d", h(0., "B"));
+        assert_eq!("∞B/s", h(f64::INFINITY, "B"));
+        let always = auto.time_per_item(TimePerItem::Always);
+        assert_eq!("2ms/file", 500.human_throughput("file").with_config(always));
+        assert_eq!("∞/it", 0.human_throughput_bare().with_config(always));
+        assert_eq!(
+            "1.2µs/b",
+            810000
+                .human_throughput_bits()
+                .with_config(always.bit_notation(crate::BitNotation::Bps))
+        );
+        let live = always.fixed_width(true);
+        assert_eq!("   2.00s/it", 0.5.human_throughput_bare().with_config(live));
+        assert_eq!(
+            "  4:10.0/it",
+            (1. / 250.).human_throughput_bare().with_config(live)
+        );
+
+        let h = HumanThroughputData::parse("4:10/file").unwrap();
+        assert_eq!((1. / 250., "file"), (h.val, h.unit()));
+        let h = HumanThroughputData::parse("2s/it").unwrap();
+        assert_eq!((0.5, ""), (h.val, h.unit()));
+        assert_eq!(0.5.human_throughput_bare(), h);
+        assert_eq!("2s/it", h.with_config(always));
+        let h = HumanThroughputData::parse("1.2µs/b").unwrap();
+        assert_eq!("bit", h.unit());
+        assert!(HumanThroughputData::parse("2x/it").is_err());
+    }
+
+    #[test]
+    fn fixed_width() {
+        use crate::HumanConfig;
+        let c = HumanConfig::new().fixed_width(true);
+        let h = |val: f64| val.human_throughput_bytes().with_config(c);
+        assert_eq!("  1.23MB/s", h(1234567.));
+        assert_eq!("999.00kB/s", h(999e3));
+        assert_eq!("30.00B/min", h(0.5));
+        assert_eq!("   9.00B/d", h(9. / 86400.));
+        assert_eq!("   1.00B/s", h(1.));
+    }
+
+    #[test]
+    fn precision() {
+        use crate::{HumanConfig, Precision};
+        let s3 = HumanConfig::new().precision(Precision::Significant(3));
+        assert_eq!("1.23MB/s", 1234567.human_throughput_bytes().with_config(s3));
+        assert_eq!("30.0B/min", 0.5.human_throughput_bytes().with_config(s3));
+        assert_eq!(
+            "9.00B/d",
+            (9. / 86400.).human_throughput_bytes().with_config(s3)
+        );
+        assert_eq!("54.00°C/h", format!("{:.2}", 0.015.human_throughput("°C")));
+        assert_eq!(
+            "1.235MB/s",
+            format!("{:.3}", 1234567.human_throughput_bytes())
+        );
+    }
+
+    #[test]
+    fn parse() {
+        use crate::{HumanParseError, HumanThroughputData};
+        let p = |s| HumanThroughputData::parse(s).map(|h| (h.val, h.unit.to_string()));
+        assert_eq!(Ok((1200000., "B".to_owned())), p("1.2MB/s"));
+        assert_eq!(Ok((500000., "B".to_owned())), p("500 kB/s"));
+        assert_eq!(Ok((0.5, "req".to_owned())), p("30req/min"));
+        assert_eq!(Ok((6.1 / 60., "tests".to_owned())), p("6.1tests/min"));
+        assert_eq!(Ok((0.015, "°C".to_owned())), p("54°C/h"));
+        assert_eq!(Ok((9. / 86400., "".to_owned())), p("9/d"));
+        assert_eq!(Ok((-1., "B".to_owned())), p("-1B/s"));
+        assert_eq!(Ok((5. / 60., "kg".to_owned())), p("5kg/min"));
+        assert_eq!(Err(HumanParseError::Empty), p(""));
+        assert_eq!(Err(HumanParseError::InvalidUnit), p("5B"));
+        assert_eq!(Err(HumanParseError::InvalidUnit), p("5B/w"));
+        assert_eq!(Err(HumanParseError::InvalidNumber), p("B/s"));
+
+        #[cfg(feature = "alloc")]
+        let h: HumanThroughputData = "1.9kB/s".parse().unwrap();
+        #[cfg(feature = "alloc")]
+        assert_eq!("1.9kB/s", h);
+    }
 }
 
 #[test]
@@ -122,5 +734,8 @@ fn serialize() -> Result<(), serde_json::Error> {
     assert_eq!(r#"{"val":123456.0,"unit":"X"}"#, &ser);
     let h2 = serde_json::from_str::<HumanThroughputData>(&ser)?;
     assert_eq!(h, h2);
+    let h = 1_005_000_000_u64.human_throughput_bytes();
+    let h2 = serde_json::from_str::<HumanThroughputData>(&serde_json::to_string(&h)?)?;
+    assert_eq!(("1.01GB/s", h.to_string()), ("1.01GB/s", h2.to_string()));
     Ok(())
 }
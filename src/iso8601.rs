@@ -0,0 +1,70 @@
+//! Serialize and deserialize [`HumanDurationData`] as ISO 8601 strings, instead of its raw value.
+//!
+//! Use it with serde's `with` attribute:
+//! ```
+//! use human_repr::{HumanDuration, HumanDurationData};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Job {
+//!     #[serde(with = "human_repr::iso8601")]
+//!     timeout: HumanDurationData,
+//! }
+//!
+//! let job = Job { timeout: 3888.395.human_duration() };
+//! assert_eq!(r#"{"timeout":"PT1H4M48.395S"}"#, serde_json::to_string(&job)?);
+//! # Ok::<(), serde_json::Error>(())
+//! ```
+
+use crate::{DurationStyle, HumanConfig, HumanDurationData};
+use core::fmt;
+use serde::{de, Deserializer, Serializer};
+
+/// Serialize a [`HumanDurationData`] as an ISO 8601 string.
+pub fn serialize<S: Serializer>(h: &HumanDurationData, serializer: S) -> Result<S::Ok, S::Error> {
+    let iso = HumanDurationData {
+        val: h.val,
+        exact: h.exact,
+        config: HumanConfig {
+            duration_style: DurationStyle::Iso8601,
+            ..h.config
+        },
+    };
+    serializer.collect_str(&iso)
+}
+
+/// Deserialize a [`HumanDurationData`] from an ISO 8601 string.
+pub fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<HumanDurationData, D::Error> {
+    deserializer.deserialize_str(Iso8601Visitor)
+}
+
+struct Iso8601Visitor;
+
+impl de::Visitor<'_> for Iso8601Visitor {
+    type Value = HumanDurationData;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an ISO 8601 duration")
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+        HumanDurationData::parse_iso8601(s).map_err(E::custom)
+    }
+}
+
+#[test]
+fn round_trip() -> Result<(), serde_json::Error> {
+    use crate::HumanDuration;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Job(#[serde(with = "self")] HumanDurationData);
+    let job = Job(1000000.5.human_duration());
+    let ser = serde_json::to_string(&job)?;
+    assert_eq!(r#""P11DT13H46M40.5S""#, &ser);
+    assert_eq!(job, serde_json::from_str(&ser)?);
+    assert!(serde_json::from_str::<Job>(r#""1:04:48""#).is_err());
+    Ok(())
+}
@@ -1,39 +1,105 @@
 #![cfg_attr(
     not(any(feature = "1024", feature = "iec", feature = "space")), 
-    doc = include_str!("../README.md")
+    doc = include_str!("../README.md"),
+    allow(clippy::doc_lazy_continuation) // the README predates this lint.
 )]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+// the README links to `std` items, which are also in `core` and `alloc`.
+#[cfg(all(doc, not(feature = "std")))]
+extern crate std;
+
+mod config;
+#[cfg(feature = "serde")]
+mod de;
 mod human_count;
 mod human_duration;
+mod human_str;
 mod human_throughput;
+#[cfg(feature = "serde")]
+pub mod iso8601;
+mod ops;
+mod parse;
+#[cfg(feature = "std")]
+mod rate;
+#[cfg(feature = "std")]
+mod stopwatch;
 mod utils;
 
-use std::borrow::Cow;
+pub use config::{
+    BitNotation, DurationStyle, HumanConfig, Precision, Prefixes, TimePerItem, TimeUnit,
+};
+pub use human_str::HumanStr;
+pub use ops::UnitMismatchError;
+pub use parse::HumanParseError;
+#[cfg(feature = "std")]
+pub use rate::RateEstimator;
+#[cfg(feature = "std")]
+pub use stopwatch::{Stopwatch, TimingGuard};
+use utils::{Exact, ToExact};
+
+#[cfg(feature = "log")]
+#[doc(hidden)]
+pub use log as __log;
 
 /// Human Count data, ready to generate Debug and Display representations.
-#[derive(PartialEq, PartialOrd)] // Debug and Display impls in the specific module.
+// PartialEq, Eq, PartialOrd, Hash, Debug, and Display impls in the specific module.
+#[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "de::CountRepr<'a>"))]
 pub struct HumanCountData<'a> {
     val: f64,
-    unit: Cow<'a, str>,
+    unit: Unit<'a>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    exact: Option<Exact>,
+    /// Whether this is the zero of an empty [`Sum`](core::iter::Sum), which adds to any unit.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    empty_sum: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    config: HumanConfig,
 }
 
 /// Human Duration data, ready to generate Debug and Display representations.
-#[derive(PartialEq, PartialOrd)] // Debug and Display impls in the specific module.
+// PartialEq, Eq, PartialOrd, Hash, Debug, and Display impls in the specific module.
+#[derive(Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "de::DurationRepr"))]
 pub struct HumanDurationData {
     val: f64,
+    /// The exact nanoseconds, when available.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    exact: Option<Exact>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    config: HumanConfig,
 }
 
 /// Human Throughput data, ready to generate Debug and Display representations.
-#[derive(PartialEq, PartialOrd)] // Debug and Display impls in the specific module.
+// PartialEq, Eq, PartialOrd, Hash, Debug, and Display impls in the specific module.
+#[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "de::ThroughputRepr<'a>"))]
 pub struct HumanThroughputData<'a> {
     val: f64,
-    unit: Cow<'a, str>,
+    unit: Unit<'a>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    exact: Option<Exact>,
+    /// Whether this is the zero of an empty [`Sum`](core::iter::Sum), which adds to any unit.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    empty_sum: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    config: HumanConfig,
 }
 
+/// The units of counts and throughputs, which can only be borrowed without the `alloc` feature.
+#[cfg(feature = "alloc")]
+type Unit<'a> = alloc::borrow::Cow<'a, str>;
+#[cfg(not(feature = "alloc"))]
+type Unit<'a> = &'a str;
+
 const BYTES: &str = "B";
+const BITS: &str = "bit";
 
 /// Human Count trait, supporting all Rust primitive number types.
 pub trait HumanCount: sealed::Sealed + Sized {
@@ -48,7 +114,7 @@ assert_eq!("4.2Mcoins", 4221432u32.human_count("coins"));
 ```
 "#
     )]
-    fn human_count<'a>(self, unit: impl Into<Cow<'a, str>>) -> HumanCountData<'a>;
+    fn human_count<'a>(self, unit: impl Into<Unit<'a>>) -> HumanCountData<'a>;
 
     /// Generate beautiful human-readable counts supporting automatic prefixes.
     #[cfg_attr(
@@ -79,6 +145,22 @@ assert_eq!("4.2MB", 4221432u32.human_count_bytes());
     fn human_count_bytes(self) -> HumanCountData<'static> {
         self.human_count(BYTES)
     }
+
+    /// Generate beautiful human-readable counts supporting automatic prefixes and bits as the unit,
+    /// rendered as `bit` or `b` according to the [`BitNotation`].
+    #[cfg_attr(
+        not(any(feature = "1024", feature = "iec", feature = "space")),
+        doc = r#"
+
+```
+use human_repr::HumanCount;
+assert_eq!("4.2Mbit", 4221432u32.human_count_bits());
+```
+"#
+    )]
+    fn human_count_bits(self) -> HumanCountData<'static> {
+        self.human_count(BITS)
+    }
 }
 
 /// Human Duration trait, supporting all Rust primitive number types and Duration.
@@ -105,6 +187,24 @@ assert_eq!("160ms", d.human_duration());
 "#
     )]
     fn human_duration(self) -> HumanDurationData;
+
+    /// Generate beautiful human-readable relative times, where negative values are in the past.
+    #[cfg_attr(
+        not(any(feature = "1024", feature = "iec", feature = "space")),
+        doc = r#"
+
+```
+use human_repr::HumanDuration;
+assert_eq!("3 minutes ago", (-180).human_relative());
+assert_eq!("in 2 hours", 7000.human_relative());
+assert_eq!("just now", 2.human_relative());
+```
+"#
+    )]
+    fn human_relative(self) -> HumanDurationData {
+        let config = HumanConfig::new().duration_style(DurationStyle::Relative);
+        self.human_duration().with_config(config)
+    }
 }
 
 /// Human Throughput trait, supporting all Rust primitive number types.
@@ -120,7 +220,7 @@ assert_eq!("1.2k°C/s", 1234.5.human_throughput("°C"));
 ```
 "#
     )]
-    fn human_throughput<'a>(self, unit: impl Into<Cow<'a, str>>) -> HumanThroughputData<'a>;
+    fn human_throughput<'a>(self, unit: impl Into<Unit<'a>>) -> HumanThroughputData<'a>;
 
     /// Generate beautiful human-readable throughputs supporting automatic prefixes.
     #[cfg_attr(
@@ -151,23 +251,44 @@ assert_eq!("1.2kB/s", 1234.5.human_throughput_bytes());
     fn human_throughput_bytes(self) -> HumanThroughputData<'static> {
         self.human_throughput(BYTES)
     }
+
+    /// Generate beautiful human-readable throughputs supporting automatic prefixes and bits as the
+    /// unit, rendered as `bit/s` or `bps` according to the [`BitNotation`].
+    #[cfg_attr(
+        not(any(feature = "1024", feature = "iec", feature = "space")),
+        doc = r#"
+
+```
+use human_repr::{BitNotation, HumanConfig, HumanThroughput};
+assert_eq!("1.23Gbit/s", 1234567890.human_throughput_bits());
+let bps = HumanConfig::new().bit_notation(BitNotation::Bps);
+assert_eq!("1.23Gbps", 1234567890.human_throughput_bits().with_config(bps));
+```
+"#
+    )]
+    fn human_throughput_bits(self) -> HumanThroughputData<'static> {
+        self.human_throughput(BITS)
+    }
 }
 
 macro_rules! impl_trait {
     {$($t:ty),+} => {$(
         impl HumanCount for $t {
-            fn human_count<'a>(self, unit: impl Into<Cow<'a, str>>) -> HumanCountData<'a> {
-                HumanCountData{val: self as f64, unit: unit.into()}
+            fn human_count<'a>(self, unit: impl Into<Unit<'a>>) -> HumanCountData<'a> {
+                let exact = self.to_exact();
+                HumanCountData{val: self as f64, unit: unit.into(), exact, empty_sum: false, config: HumanConfig::new()}
             }
         }
         impl HumanDuration for $t {
             fn human_duration(self) -> HumanDurationData {
-                HumanDurationData{val: self as f64}
+                let exact = self.to_exact().and_then(|e| e.checked_mul(human_duration::NANOS));
+                HumanDurationData{val: self as f64, exact, config: HumanConfig::new()}
             }
         }
         impl HumanThroughput for $t {
-            fn human_throughput<'a>(self, unit: impl Into<Cow<'a, str>>) -> HumanThroughputData<'a> {
-                HumanThroughputData{val: self as f64, unit: unit.into()}
+            fn human_throughput<'a>(self, unit: impl Into<Unit<'a>>) -> HumanThroughputData<'a> {
+                let exact = self.to_exact();
+                HumanThroughputData{val: self as f64, unit: unit.into(), exact, empty_sum: false, config: HumanConfig::new()}
             }
         }
     )+}
@@ -175,7 +296,7 @@ macro_rules! impl_trait {
 impl_trait!(u8, u16, u32, u64, u128, usize, f32, f64, i8, i16, i32, i64, i128, isize);
 
 mod sealed {
-    use std::time::Duration;
+    use core::time::Duration;
 
     pub trait Sealed {}
     macro_rules! impl_sealed {
@@ -0,0 +1,384 @@
+use super::{HumanConfig, HumanCountData, HumanDurationData, HumanThroughputData};
+use crate::utils::Exact;
+use crate::Unit;
+use core::fmt;
+use core::iter::Sum;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+use core::time::Duration;
+
+/// The error returned when adding or subtracting counts or throughputs of different units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnitMismatchError;
+
+impl fmt::Display for UnitMismatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot combine values of different units")
+    }
+}
+
+impl core::error::Error for UnitMismatchError {}
+
+/// The exact sum of two values, which is only known when both of them are.
+fn exact_add(a: Option<Exact>, b: Option<Exact>) -> Option<Exact> {
+    a?.checked_add(b?)
+}
+
+/// The exact product of a value by a scalar, which must be whole.
+fn exact_mul(a: Option<Exact>, k: f64) -> Option<Exact> {
+    let (a, k) = (a?, Exact::from_whole(k)?);
+    a.mag
+        .checked_mul(k.mag)
+        .map(|mag| Exact::new(a.neg != k.neg, mag))
+}
+
+/// The exact quotient of a value by a scalar, which must be whole and divide it evenly.
+fn exact_div(a: Option<Exact>, k: f64) -> Option<Exact> {
+    let (a, k) = (a?, Exact::from_whole(k)?);
+    (k.mag != 0 && a.mag % k.mag == 0).then(|| Exact::new(a.neg != k.neg, a.mag / k.mag))
+}
+
+/// Implement the arithmetic operators on a data type with units, which panic when the units
+/// differ, and its checked variants.
+macro_rules! impl_unit_ops {
+    ($t:ident, $name:literal) => {
+        impl<'a> $t<'a> {
+            #[doc = concat!("Add another ", $name, " of the same unit, or fail when the units differ.")]
+            ///
+            /// The zero sum of an empty iterator adds to any unit, and takes its [`HumanConfig`].
+            /// Otherwise, the result keeps the [`HumanConfig`] of `self`.
+            pub fn checked_add(self, rhs: Self) -> Result<Self, UnitMismatchError> {
+                let (base, rhs) = match self.empty_sum {
+                    true => (rhs, self),
+                    false => (self, rhs),
+                };
+                if base.unit != rhs.unit && !rhs.empty_sum {
+                    return Err(UnitMismatchError);
+                }
+                Ok($t {
+                    val: base.val + rhs.val,
+                    exact: exact_add(base.exact, rhs.exact),
+                    empty_sum: base.empty_sum && rhs.empty_sum,
+                    ..base
+                })
+            }
+
+            #[doc = concat!("Subtract another ", $name, " of the same unit, or fail when the units differ.")]
+            ///
+            /// Just like [`checked_add`](Self::checked_add), the zero sum of an empty iterator works
+            /// with any unit.
+            pub fn checked_sub(self, rhs: Self) -> Result<Self, UnitMismatchError> {
+                self.checked_add(-rhs)
+            }
+
+            #[doc = concat!("Sum ", $name, "s of the given unit, so an empty iterator sums to a zero of it.")]
+            ///
+            /// Panics when the units differ, just like [`Sum`] does.
+            pub fn sum_in<I>(iter: I, unit: impl Into<Unit<'a>>) -> Self
+            where
+                I: IntoIterator<Item = Self>,
+            {
+                let zero = $t {
+                    val: 0.,
+                    unit: unit.into(),
+                    exact: Some(Exact::new(false, 0)),
+                    empty_sum: false,
+                    config: HumanConfig::new(),
+                };
+                iter.into_iter().fold(zero, Add::add)
+            }
+        }
+
+        /// Panics when the units differ, see [`checked_add`](Self::checked_add).
+        impl<'a> Add for $t<'a> {
+            type Output = $t<'a>;
+
+            fn add(self, rhs: $t<'a>) -> $t<'a> {
+                match self.checked_add(rhs) {
+                    Ok(sum) => sum,
+                    Err(err) => panic!("{}", err),
+                }
+            }
+        }
+
+        /// Panics when the units differ, see [`checked_sub`](Self::checked_sub).
+        impl<'a> Sub for $t<'a> {
+            type Output = $t<'a>;
+
+            fn sub(self, rhs: $t<'a>) -> $t<'a> {
+                self + -rhs
+            }
+        }
+
+        impl<'a> Mul<f64> for $t<'a> {
+            type Output = $t<'a>;
+
+            fn mul(self, rhs: f64) -> $t<'a> {
+                $t {
+                    val: self.val * rhs,
+                    exact: exact_mul(self.exact, rhs),
+                    ..self
+                }
+            }
+        }
+
+        impl<'a> Div<f64> for $t<'a> {
+            type Output = $t<'a>;
+
+            fn div(self, rhs: f64) -> $t<'a> {
+                $t {
+                    val: self.val / rhs,
+                    exact: exact_div(self.exact, rhs),
+                    ..self
+                }
+            }
+        }
+
+        impl<'a> Neg for $t<'a> {
+            type Output = $t<'a>;
+
+            fn neg(self) -> $t<'a> {
+                $t {
+                    val: -self.val,
+                    exact: self.exact.map(Neg::neg),
+                    ..self
+                }
+            }
+        }
+
+        /// Panics when the units differ, while an empty iterator sums to a zero without unit, which
+        /// adds to any unit unlike other unit-less zeros, or see [`sum_in`](Self::sum_in).
+        impl<'a> Sum for $t<'a> {
+            fn sum<I: Iterator<Item = $t<'a>>>(iter: I) -> $t<'a> {
+                iter.reduce(Add::add).unwrap_or_else(|| $t {
+                    val: 0.,
+                    unit: Unit::from(""),
+                    exact: Some(Exact::new(false, 0)),
+                    empty_sum: true,
+                    config: HumanConfig::new(),
+                })
+            }
+        }
+    };
+}
+
+impl_unit_ops!(HumanCountData, "count");
+impl_unit_ops!(HumanThroughputData, "throughput");
+
+/// The result keeps the [`HumanConfig`] of `self`.
+impl Add for HumanDurationData {
+    type Output = HumanDurationData;
+
+    fn add(self, rhs: HumanDurationData) -> HumanDurationData {
+        HumanDurationData {
+            val: self.val + rhs.val,
+            exact: exact_add(self.exact, rhs.exact),
+            ..self
+        }
+    }
+}
+
+/// The result keeps the [`HumanConfig`] of `self`.
+impl Sub for HumanDurationData {
+    type Output = HumanDurationData;
+
+    fn sub(self, rhs: HumanDurationData) -> HumanDurationData {
+        self + -rhs
+    }
+}
+
+impl Mul<f64> for HumanDurationData {
+    type Output = HumanDurationData;
+
+    fn mul(self, rhs: f64) -> HumanDurationData {
+        HumanDurationData {
+            val: self.val * rhs,
+            exact: exact_mul(self.exact, rhs),
+            ..self
+        }
+    }
+}
+
+impl Div<f64> for HumanDurationData {
+    type Output = HumanDurationData;
+
+    fn div(self, rhs: f64) -> HumanDurationData {
+        HumanDurationData {
+            val: self.val / rhs,
+            exact: exact_div(self.exact, rhs),
+            ..self
+        }
+    }
+}
+
+impl Neg for HumanDurationData {
+    type Output = HumanDurationData;
+
+    fn neg(self) -> HumanDurationData {
+        HumanDurationData {
+            val: -self.val,
+            exact: self.exact.map(Neg::neg),
+            ..self
+        }
+    }
+}
+
+/// An empty iterator sums to zero.
+impl Sum for HumanDurationData {
+    fn sum<I: Iterator<Item = HumanDurationData>>(iter: I) -> HumanDurationData {
+        iter.reduce(Add::add)
+            .unwrap_or_else(|| Duration::ZERO.into())
+    }
+}
+
+/// The throughput of a count over a duration, in the unit of the count, which keeps its
+/// [`HumanConfig`].
+///
+/// Exact counts and durations use the integer math of
+/// [`from_count_and_duration`](HumanThroughputData::from_count_and_duration), while the others
+/// just divide, so a zero duration makes an infinite or NaN throughput.
+impl<'a> Div<HumanDurationData> for HumanCountData<'a> {
+    type Output = HumanThroughputData<'a>;
+
+    fn div(self, rhs: HumanDurationData) -> HumanThroughputData<'a> {
+        let count = self.exact.filter(|e| !e.neg).map(|e| u64::try_from(e.mag));
+        let elapsed = rhs
+            .exact
+            .filter(|e| !e.neg)
+            .map(|_| Duration::try_from(rhs));
+        match (count, elapsed) {
+            (Some(Ok(count)), Some(Ok(elapsed))) => {
+                HumanThroughputData::from_count_and_duration(count, elapsed, self.unit)
+                    .with_config(self.config)
+            }
+            _ => HumanThroughputData {
+                val: self.val / rhs.val,
+                unit: self.unit,
+                exact: None,
+                empty_sum: false,
+                config: self.config,
+            },
+        }
+    }
+}
+
+#[cfg(all(test, not(any(feature = "1024", feature = "iec", feature = "space"))))]
+mod tests {
+    use super::*;
+    use crate::{HumanCount, HumanDuration, HumanThroughput};
+
+    #[test]
+    fn count() {
+        let (a, b) = (1234567.human_count_bytes(), 1000.human_count_bytes());
+        assert_eq!("1.2MB", a.clone() + b.clone());
+        assert_eq!(1233567., (a.clone() - b.clone()).value());
+        assert_eq!("-1.2MB", -a.clone());
+        assert_eq!("2.5MB", a.clone() * 2.);
+        assert_eq!("617.3kB", a.clone() / 2.);
+        let files = 5.human_count("files");
+        assert_eq!(Err(UnitMismatchError), a.clone().checked_add(files.clone()));
+        assert_eq!(Err(UnitMismatchError), a.checked_sub(files));
+        assert_eq!("6kB", b.checked_add(5000.human_count_bytes()).unwrap());
+    }
+
+    #[test]
+    #[should_panic(expected = "different units")]
+    fn count_mismatch() {
+        let _ = 1.human_count_bytes() + 1.human_count("files");
+    }
+
+    #[test]
+    fn exact() {
+        let big = u64::MAX.human_count_bytes();
+        let sum = big.clone() + 1.human_count_bytes() - 2.human_count_bytes();
+        assert_eq!(Some(Exact::new(false, u64::MAX as u128 - 1)), sum.exact);
+        let twice = -big.clone() * 2.;
+        assert_eq!(Some(Exact::new(true, u64::MAX as u128 * 2)), twice.exact);
+        assert_eq!(big.exact, (twice.clone() / -2.).exact);
+        assert_eq!(None, (twice.clone() * 0.5).exact);
+        assert_eq!(None, (twice / 4.).exact);
+
+        let d = Duration::new(1, 1).human_duration() + Duration::new(2, 2).human_duration();
+        assert_eq!(Ok(Duration::new(3, 3)), Duration::try_from(d));
+        assert_eq!(Ok(Duration::new(1, 1)), Duration::try_from(d / 3.));
+        let back = -Duration::new(1, 0).human_duration() + Duration::new(3, 3).human_duration();
+        assert_eq!(Ok(Duration::new(2, 3)), Duration::try_from(back));
+    }
+
+    #[test]
+    fn duration() {
+        let (a, b) = (3888.human_duration(), 1.5.human_duration() * 60.);
+        assert_eq!("1:06:18", a + b);
+        assert_eq!("1:03:18", a - b);
+        assert_eq!("2:09:36", a * 2.);
+        assert_eq!("32:24", a / 2.);
+        assert_eq!("-1:30", -b);
+    }
+
+    #[test]
+    fn throughput() {
+        let (a, b) = (
+            1234567.human_throughput_bytes(),
+            1000.human_throughput_bytes(),
+        );
+        assert_eq!("1.2MB/s", a.clone() + b.clone());
+        assert_eq!("1.2MB/s", a.clone() - b);
+        assert_eq!("-1.2MB/s", -a.clone());
+        assert_eq!("2.5MB/s", a.clone() * 2.);
+        assert_eq!("617.3kB/s", a.clone() / 2.);
+        let files = 5.human_throughput("files");
+        assert_eq!(Err(UnitMismatchError), a.checked_add(files));
+    }
+
+    #[test]
+    fn sum() {
+        let counts = [1000, 2000, 3000].map(|n| n.human_count_bytes());
+        assert_eq!("6kB", counts.into_iter().sum::<HumanCountData>());
+        let empty = core::iter::empty::<HumanCountData>().sum::<HumanCountData>();
+        assert_eq!((0., ""), (empty.value(), empty.unit()));
+        let bytes = empty.clone() + 1500.human_count_bytes();
+        assert_eq!(
+            ("1.5kB", Some(Exact::new(false, 1500))),
+            (&*bytes.to_string(), bytes.exact)
+        );
+        assert_eq!("-1.5kB", empty.clone() - 1500.human_count_bytes());
+        let bare = 0.human_count_bare();
+        assert_eq!(
+            Err(UnitMismatchError),
+            bare.clone().checked_add(5.human_count_bytes())
+        );
+        assert_eq!(
+            Err(UnitMismatchError),
+            5.human_count_bytes().checked_sub(bare)
+        );
+        assert_eq!("-5", empty.clone() * 2. - 5.human_count_bare());
+        let both = empty.clone() + empty.clone();
+        assert_eq!("7B", both + 7.human_count_bytes());
+        let none = core::iter::empty();
+        assert_eq!("0B", HumanCountData::sum_in(none, "B"));
+        let counts = [1000, 2000].map(|n| n.human_count_bytes());
+        assert_eq!("3kB", HumanCountData::sum_in(counts, "B"));
+
+        let rates = [1.5, 2.5].map(|n| n.human_throughput("files"));
+        assert_eq!("4files/s", rates.into_iter().sum::<HumanThroughputData>());
+        let none = core::iter::empty();
+        assert_eq!("0files/d", HumanThroughputData::sum_in(none, "files"));
+
+        let durations = [30, 40, 50].map(|n| n.human_duration());
+        assert_eq!("2:00", durations.into_iter().sum::<HumanDurationData>());
+        assert_eq!("0ns", core::iter::empty().sum::<HumanDurationData>());
+    }
+
+    #[test]
+    fn throughput_of_count() {
+        let rate = 3431237.human_count_bytes() / Duration::from_millis(1500).human_duration();
+        assert_eq!("2.3MB/s", rate);
+        let exact = u64::MAX.human_count("it") / Duration::from_secs(1).human_duration();
+        assert_eq!(Some(Exact::new(false, u64::MAX as u128)), exact.exact);
+        let rate = 90.human_count("files") / 605.human_duration();
+        assert_eq!("8.9files/min", rate);
+        assert_eq!(
+            "∞B/s",
+            5.human_count_bytes() / Duration::ZERO.human_duration()
+        );
+    }
+}
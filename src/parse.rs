@@ -0,0 +1,95 @@
+#[cfg(not(any(feature = "std", test)))]
+use crate::utils::Float;
+use crate::utils::SliceWriter;
+use core::error;
+use core::fmt::{self, Write};
+
+/// The error returned when parsing a human representation fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HumanParseError {
+    /// The input was empty.
+    Empty,
+    /// The input did not start with a valid number.
+    InvalidNumber,
+    /// The unit or suffix was missing or not recognized.
+    InvalidUnit,
+    /// The input did not match any of the supported formats.
+    InvalidFormat,
+}
+
+impl fmt::Display for HumanParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HumanParseError::Empty => write!(f, "cannot parse an empty string"),
+            HumanParseError::InvalidNumber => write!(f, "invalid number"),
+            HumanParseError::InvalidUnit => write!(f, "missing or unknown unit"),
+            HumanParseError::InvalidFormat => write!(f, "unrecognized format"),
+        }
+    }
+}
+
+impl error::Error for HumanParseError {}
+
+/// Split a leading decimal number from the input, returning it along with the remaining text,
+/// which has its leading whitespace skipped.
+///
+/// The number can have a lowercase exponent like `3.4e38`, which is only taken when followed
+/// by digits, so units starting with an `e` still work.
+pub fn split_number(s: &str) -> Result<(&str, &str), HumanParseError> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(HumanParseError::Empty);
+    }
+
+    let bytes = s.as_bytes();
+    let digits = |from: usize| {
+        from + bytes[from..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count()
+    };
+    let start = matches!(bytes[0], b'+' | b'-') as usize;
+    let mut end = digits(start);
+    let mut valid = end > start;
+    if bytes.get(end) == Some(&b'.') && digits(end + 1) > end + 1 {
+        end = digits(end + 1);
+        valid = true;
+    }
+    if valid && bytes.get(end) == Some(&b'e') {
+        let sign = matches!(bytes.get(end + 1), Some(b'+' | b'-')) as usize;
+        let exp = digits(end + 1 + sign);
+        if exp > end + 1 + sign {
+            end = exp;
+        }
+    }
+    match valid {
+        true => Ok((&s[..end], s[end..].trim_start())),
+        false => Err(HumanParseError::InvalidNumber),
+    }
+}
+
+/// Parse a decimal number as split by [`split_number`], multiplied by `10^exp`.
+///
+/// The exponent is applied before parsing whenever possible, so the result is correctly rounded.
+pub fn parse_scaled(num: &str, exp: i32) -> f64 {
+    let (num, exp) = match num.split_once('e') {
+        Some((num, e)) => {
+            // huge exponents saturate, which still parse to zero or infinity.
+            let e = match (e.parse(), e.starts_with('-')) {
+                (Ok(e), _) => e,
+                (Err(_), true) => i32::MIN,
+                (Err(_), false) => i32::MAX,
+            };
+            (num, exp.saturating_add(e))
+        }
+        None => (num, exp),
+    };
+    let mut buf = [0u8; 48];
+    let mut w = SliceWriter::new(&mut buf);
+    match write!(w, "{}e{}", num, exp) {
+        Ok(()) => w.as_str().parse(),
+        Err(_) => num.parse().map(|x: f64| x * 10f64.powi(exp)),
+    }
+    .unwrap() // the number was validated by split_number.
+}
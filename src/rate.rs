@@ -0,0 +1,200 @@
+use crate::{HumanConfig, HumanDuration, HumanDurationData, HumanThroughputData};
+use std::borrow::Cow;
+use std::time::Instant;
+
+/// A smoothed rate and ETA estimator for progress reporting, fed with `(position, Instant)`
+/// samples.
+///
+/// The rate is an exponentially weighted moving average, which smooths both the position and
+/// time deltas like `tqdm` does, so irregular updates are weighted by how long they took.
+#[cfg_attr(
+    not(any(feature = "1024", feature = "iec", feature = "space")),
+    doc = r#"
+
+```
+use human_repr::RateEstimator;
+use std::time::{Duration, Instant};
+
+let start = Instant::now();
+let mut rate = RateEstimator::new("B").total(10_000_000);
+rate.update(0, start);
+rate.update(2_000_000, start + Duration::from_secs(1));
+assert_eq!("2MB/s", rate.rate().unwrap());
+assert_eq!("4s", rate.eta().unwrap());
+```
+"#
+)]
+#[derive(Debug, Clone)]
+pub struct RateEstimator {
+    unit: Cow<'static, str>,
+    smoothing: f64,
+    total: Option<u64>,
+    config: HumanConfig,
+    last: Option<(u64, Instant)>,
+    /// The smoothed position and time deltas, whose ratio is the rate.
+    ema: Option<(f64, f64)>,
+}
+
+impl RateEstimator {
+    /// Create a new estimator of throughputs in the given unit, with a smoothing of `0.3`.
+    pub fn new(unit: impl Into<Cow<'static, str>>) -> Self {
+        RateEstimator {
+            unit: unit.into(),
+            smoothing: 0.3,
+            total: None,
+            config: HumanConfig::new(),
+            last: None,
+            ema: None,
+        }
+    }
+
+    /// Set the weight of new samples, from `0` for the overall average rate, to `1` for the
+    /// instantaneous rate of the last sample.
+    pub fn smoothing(mut self, smoothing: f64) -> Self {
+        self.smoothing = smoothing.clamp(0., 1.);
+        self
+    }
+
+    /// Set the total position, which enables the ETA.
+    pub fn total(mut self, total: u64) -> Self {
+        self.total = Some(total);
+        self
+    }
+
+    /// Render the rate and ETA with a custom [`HumanConfig`], instead of the default one.
+    pub fn with_config(mut self, config: HumanConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Feed the current position, at the time it was reached.
+    ///
+    /// Samples without elapsed time are merged into the next one, while a position going
+    /// backwards restarts the estimation from it.
+    pub fn update(&mut self, position: u64, now: Instant) {
+        let Some((last_pos, last_time)) = self.last else {
+            self.last = Some((position, now));
+            return;
+        };
+        if position < last_pos {
+            (self.last, self.ema) = (Some((position, now)), None);
+            return;
+        }
+        let dt = now.saturating_duration_since(last_time).as_secs_f64();
+        if dt == 0. {
+            return;
+        }
+
+        let (dn, a) = ((position - last_pos) as f64, self.smoothing);
+        let smooth = |ema: f64, x: f64| match a {
+            0. => ema + x,
+            _ => a * x + (1. - a) * ema,
+        };
+        self.ema = Some(match self.ema {
+            None => (dn, dt),
+            Some((n, t)) => (smooth(n, dn), smooth(t, dt)),
+        });
+        self.last = Some((position, now));
+    }
+
+    /// The last position fed, if any.
+    pub fn position(&self) -> Option<u64> {
+        self.last.map(|(position, _)| position)
+    }
+
+    /// The smoothed rate, after at least two samples.
+    pub fn rate(&self) -> Option<HumanThroughputData<'_>> {
+        let (n, t) = self.ema?;
+        Some(HumanThroughputData {
+            val: n / t,
+            unit: Cow::Borrowed(&self.unit),
+            exact: None,
+            empty_sum: false,
+            config: self.config,
+        })
+    }
+
+    /// The estimated time to reach the total at the smoothed rate, which is zero once reached,
+    /// and unknown while stalled.
+    pub fn eta(&self) -> Option<HumanDurationData> {
+        let remaining = self.total?.saturating_sub(self.position()?);
+        let secs = match remaining {
+            0 => 0.,
+            _ => match self.rate()?.val {
+                rate if rate > 0. => remaining as f64 / rate,
+                _ => return None,
+            },
+        };
+        Some(secs.human_duration().with_config(self.config))
+    }
+}
+
+#[cfg(all(test, not(any(feature = "1024", feature = "iec", feature = "space"))))]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn feed(rate: &mut RateEstimator, samples: &[(u64, u64)]) -> Instant {
+        let start = Instant::now();
+        for &(position, millis) in samples {
+            rate.update(position, start + Duration::from_millis(millis));
+        }
+        start
+    }
+
+    #[test]
+    fn smoothing() {
+        let mut rate = RateEstimator::new("B");
+        assert!(rate.rate().is_none());
+        feed(&mut rate, &[(0, 0)]);
+        assert!(rate.rate().is_none());
+
+        let samples = [(0, 0), (100, 1000), (400, 2000)];
+        let mut rate = RateEstimator::new("B");
+        feed(&mut rate, &samples);
+        assert_eq!("160B/s", rate.rate().unwrap());
+        let mut last = RateEstimator::new("B").smoothing(1.);
+        feed(&mut last, &samples);
+        assert_eq!("300B/s", last.rate().unwrap());
+        let mut average = RateEstimator::new("B").smoothing(0.);
+        feed(&mut average, &samples);
+        assert_eq!("200B/s", average.rate().unwrap());
+    }
+
+    #[test]
+    fn irregular() {
+        let mut rate = RateEstimator::new("it").smoothing(0.);
+        feed(&mut rate, &[(0, 0), (10, 0), (20, 500), (50, 2000)]);
+        assert_eq!("25it/s", rate.rate().unwrap());
+        assert_eq!(Some(50), rate.position());
+
+        let start = feed(&mut rate, &[(5, 0), (15, 1000)]);
+        assert_eq!("10it/s", rate.rate().unwrap());
+        rate.update(20, start);
+        assert_eq!("10it/s", rate.rate().unwrap());
+    }
+
+    #[test]
+    fn eta() {
+        let mut rate = RateEstimator::new("B").total(1000).smoothing(1.);
+        assert!(rate.eta().is_none());
+        let start = feed(&mut rate, &[(0, 0), (100, 1000)]);
+        assert_eq!("9s", rate.eta().unwrap());
+        rate.update(100, start + Duration::from_secs(2));
+        assert_eq!("0B/d", rate.rate().unwrap());
+        assert!(rate.eta().is_none());
+        rate.update(1200, start + Duration::from_secs(3));
+        assert_eq!("0ns", rate.eta().unwrap());
+        assert!(RateEstimator::new("B").eta().is_none());
+    }
+
+    #[test]
+    fn config() {
+        use crate::{HumanConfig, TimePerItem};
+        let slow = HumanConfig::new().time_per_item(TimePerItem::Auto);
+        let mut rate = RateEstimator::new("file").total(10).with_config(slow);
+        feed(&mut rate, &[(0, 0), (1, 250_000)]);
+        assert_eq!("4:10/file", rate.rate().unwrap());
+        assert_eq!("37:30", rate.eta().unwrap());
+    }
+}
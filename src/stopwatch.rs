@@ -0,0 +1,222 @@
+use crate::{HumanConfig, HumanDuration, HumanDurationData};
+use std::fmt;
+use std::time::Instant;
+
+/// A stopwatch with laps, which reports its times as [`HumanDurationData`].
+#[cfg_attr(
+    not(any(feature = "1024", feature = "iec", feature = "space")),
+    doc = r#"
+
+```
+use human_repr::Stopwatch;
+
+let mut sw = Stopwatch::start();
+// load something...
+println!("loaded in {}", sw.lap());
+// process it...
+println!("processed in {}, total {}", sw.lap(), sw.elapsed());
+```
+"#
+)]
+#[derive(Debug, Clone)]
+pub struct Stopwatch {
+    start: Instant,
+    lap: Instant,
+    config: HumanConfig,
+}
+
+impl Stopwatch {
+    /// Start a new stopwatch now.
+    pub fn start() -> Self {
+        let now = Instant::now();
+        Stopwatch {
+            start: now,
+            lap: now,
+            config: HumanConfig::new(),
+        }
+    }
+
+    /// Report the times with a custom [`HumanConfig`], instead of the default one.
+    pub fn with_config(mut self, config: HumanConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// The time elapsed since the start.
+    pub fn elapsed(&self) -> HumanDurationData {
+        self.start
+            .elapsed()
+            .human_duration()
+            .with_config(self.config)
+    }
+
+    /// The time elapsed since the previous lap, or the start, which begins a new lap.
+    pub fn lap(&mut self) -> HumanDurationData {
+        let now = Instant::now();
+        let lap = now.duration_since(self.lap);
+        self.lap = now;
+        lap.human_duration().with_config(self.config)
+    }
+
+    /// Create a guard that reports the time elapsed since the start when dropped, even on
+    /// panics, to measure a whole scope.
+    #[cfg_attr(
+        not(any(feature = "1024", feature = "iec", feature = "space")),
+        doc = r#"
+
+```
+use human_repr::Stopwatch;
+
+{
+    let _guard = Stopwatch::start().guard(|elapsed| eprintln!("scope took {}", elapsed));
+    // do something...
+}
+```
+"#
+    )]
+    pub fn guard<F: FnOnce(HumanDurationData)>(self, report: F) -> TimingGuard<F> {
+        TimingGuard {
+            stopwatch: self,
+            report: Some(report),
+        }
+    }
+}
+
+/// A guard that reports the time elapsed since its [`Stopwatch`] started when dropped.
+#[must_use = "the guard reports when dropped, so it must be bound to a variable"]
+pub struct TimingGuard<F: FnOnce(HumanDurationData)> {
+    stopwatch: Stopwatch,
+    report: Option<F>,
+}
+
+impl<F: FnOnce(HumanDurationData)> fmt::Debug for TimingGuard<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimingGuard")
+            .field("stopwatch", &self.stopwatch)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F: FnOnce(HumanDurationData)> Drop for TimingGuard<F> {
+    fn drop(&mut self) {
+        if let Some(report) = self.report.take() {
+            report(self.stopwatch.elapsed());
+        }
+    }
+}
+
+/// Measure the time to evaluate an expression, and report it through [`HumanDurationData`],
+/// evaluating to the expression value.
+///
+/// With a label, the time is printed to stderr, or logged with `log` at the info level, which
+/// requires the `log` feature. Labels other than literals, like variables, need an explicit
+/// `eprint` or `log` prefix. With a closure, it receives the time instead.
+#[cfg_attr(
+    not(any(feature = "1024", feature = "iec", feature = "space")),
+    doc = r#"
+
+```
+use human_repr::timed;
+
+let sum = timed!("summing", (1..=100).sum::<u32>()); // prints "summing took 1.2µs".
+assert_eq!(5050, sum);
+
+let label = format!("summing {} numbers", 10);
+let sum = timed!(eprint label, (1..=10).sum::<u32>()); // prints "summing 10 numbers took 1µs".
+assert_eq!(55, sum);
+
+let mut elapsed = None;
+let product = timed!(|t| elapsed = Some(t), (1..=5).product::<u32>());
+assert_eq!(120, product);
+assert!(elapsed.is_some());
+```
+"#
+)]
+#[macro_export]
+macro_rules! timed {
+    (log $label:expr, $body:expr) => {
+        $crate::timed!(
+            |elapsed| $crate::__log::info!("{} took {}", $label, elapsed),
+            $body
+        )
+    };
+    (eprint $label:expr, $body:expr) => {
+        $crate::timed!(|elapsed| eprintln!("{} took {}", $label, elapsed), $body)
+    };
+    ($label:literal, $body:expr) => {
+        $crate::timed!(eprint $label, $body)
+    };
+    ($report:expr, $body:expr) => {{
+        let _guard = $crate::Stopwatch::start().guard($report);
+        $body
+    }};
+}
+
+#[cfg(all(test, not(any(feature = "1024", feature = "iec", feature = "space"))))]
+mod tests {
+    use super::*;
+    use crate::{DurationStyle, HumanConfig, HumanDuration};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn laps() {
+        let mut sw = Stopwatch::start();
+        thread::sleep(Duration::from_millis(10));
+        let first = sw.lap();
+        thread::sleep(Duration::from_millis(5));
+        let second = sw.lap();
+        let total = sw.elapsed();
+        assert!(first.val >= 0.01 && second.val >= 0.005);
+        assert!(total.val >= first.val + second.val);
+    }
+
+    #[test]
+    fn config() {
+        let long = HumanConfig::new().duration_style(DurationStyle::Long);
+        let mut sw = Stopwatch::start().with_config(long);
+        let (lap, elapsed) = (sw.lap().config(), sw.elapsed().config());
+        assert_eq!((long, long), (lap, elapsed));
+        let fixed = Duration::from_secs(5).human_duration().with_config(elapsed);
+        assert_eq!("5 seconds", fixed);
+    }
+
+    #[test]
+    fn guard() {
+        let mut reported = None;
+        {
+            let _guard = Stopwatch::start().guard(|t| reported = Some(t));
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(reported.unwrap().val >= 0.01);
+
+        let reported = std::cell::Cell::new(false);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _guard = Stopwatch::start().guard(|_| reported.set(true));
+            panic!("boom");
+        }));
+        assert!(result.is_err() && reported.get());
+    }
+
+    #[test]
+    fn timed() {
+        let mut reported = None;
+        let value = crate::timed!(|t| reported = Some(t), {
+            thread::sleep(Duration::from_millis(10));
+            42
+        });
+        assert_eq!(42, value);
+        assert!(reported.unwrap().val >= 0.01);
+        assert_eq!(7, crate::timed!("adding", 3 + 4));
+        let label = "adding";
+        assert_eq!(7, crate::timed!(eprint label, 3 + 4));
+        assert_eq!(7, crate::timed!(eprint format!("{} again", label), 3 + 4));
+    }
+
+    #[test]
+    #[cfg(feature = "log")]
+    fn timed_log() {
+        let label = "logging";
+        assert_eq!(7, crate::timed!(log label, 3 + 4));
+    }
+}
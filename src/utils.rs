@@ -1,22 +1,434 @@
-use std::fmt;
+use crate::{HumanConfig, Precision};
+use core::cmp::Ordering;
+use core::fmt::{self, Write};
+use core::hash::{Hash, Hasher};
+use core::ops::Neg;
 
-pub const SPACE: &str = {
-    match cfg!(feature = "space") {
-        true => " ",
-        false => "",
+/// An exact integer as a sign and magnitude, so integers render without the f64 precision loss.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Exact {
+    pub neg: bool,
+    pub mag: u128,
+}
+
+impl Exact {
+    pub fn new(neg: bool, mag: u128) -> Self {
+        Exact {
+            neg: neg && mag != 0,
+            mag,
+        }
+    }
+
+    pub fn checked_mul(self, rhs: u128) -> Option<Self> {
+        self.mag.checked_mul(rhs).map(|mag| Exact { mag, ..self })
+    }
+
+    pub fn checked_add(self, rhs: Exact) -> Option<Self> {
+        match (self.neg == rhs.neg, self.mag >= rhs.mag) {
+            (true, _) => self
+                .mag
+                .checked_add(rhs.mag)
+                .map(|mag| Exact { mag, ..self }),
+            (false, true) => Some(Exact::new(self.neg, self.mag - rhs.mag)),
+            (false, false) => Some(Exact::new(rhs.neg, rhs.mag - self.mag)),
+        }
+    }
+
+    /// A whole float as an exact integer, so scaling by it can keep exact values exact.
+    pub fn from_whole(val: f64) -> Option<Self> {
+        // from 2^64 on, whole floats may not be the integers they were meant to be anyway.
+        (val.floor() == val && val.abs() < 18446744073709551616.)
+            .then(|| Exact::new(val < 0., val.abs() as u128))
+    }
+}
+
+impl Ord for Exact {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.neg, other.neg) {
+            (false, false) => self.mag.cmp(&other.mag),
+            (true, true) => other.mag.cmp(&self.mag),
+            (neg, _) => other.neg.cmp(&neg), // zero is never negative.
+        }
+    }
+}
+
+impl PartialOrd for Exact {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for Exact {
+    type Output = Exact;
+
+    fn neg(self) -> Exact {
+        Exact::new(!self.neg, self.mag)
+    }
+}
+
+/// Convert primitive numbers into [`Exact`], which is only available for integers.
+pub trait ToExact {
+    fn to_exact(self) -> Option<Exact>;
+}
+
+macro_rules! impl_to_exact {
+    {$($u:ty),+; $($i:ty),+; $($f:ty),+} => {
+        $(impl ToExact for $u {
+            fn to_exact(self) -> Option<Exact> {
+                Some(Exact::new(false, self as u128))
+            }
+        })+
+        $(impl ToExact for $i {
+            fn to_exact(self) -> Option<Exact> {
+                Some(Exact::new(self < 0, self.unsigned_abs() as u128))
+            }
+        })+
+        $(impl ToExact for $f {
+            fn to_exact(self) -> Option<Exact> {
+                None
+            }
+        })+
+    }
+}
+impl_to_exact!(u8, u16, u32, u64, u128, usize; i8, i16, i32, i64, i128, isize; f32, f64);
+
+/// The float methods used from `std`, which `core` doesn't have, for `no_std` builds.
+#[cfg(not(feature = "std"))]
+pub trait Float {
+    fn floor(self) -> f64;
+    fn round(self) -> f64;
+    fn powi(self, n: i32) -> f64;
+    /// An approximation within 0.03, only used to find decimal exponents, which are corrected.
+    fn log10(self) -> f64;
+}
+
+#[cfg(not(feature = "std"))]
+impl Float for f64 {
+    fn floor(self) -> f64 {
+        // from 2^52 on, all values are integers, just like non-finite ones are kept.
+        if !self.is_finite() || self.abs() >= 4503599627370496. {
+            return self;
+        }
+        let t = self as i64 as f64;
+        if t > self {
+            t - 1.
+        } else {
+            t
+        }
+    }
+
+    fn round(self) -> f64 {
+        if !self.is_finite() || self.abs() >= 4503599627370496. {
+            return self;
+        }
+        let t = self as i64 as f64;
+        match self - t {
+            frac if frac >= 0.5 => t + 1.,
+            frac if frac <= -0.5 => t - 1.,
+            _ => t,
+        }
     }
-};
+
+    fn powi(self, n: i32) -> f64 {
+        // the same square-and-multiply as the compiler builtin used by `std`.
+        let (mut base, mut e, mut r) = (self, n.unsigned_abs(), 1.);
+        loop {
+            if e & 1 == 1 {
+                r *= base;
+            }
+            e >>= 1;
+            if e == 0 {
+                break;
+            }
+            base *= base;
+        }
+        if n < 0 {
+            1. / r
+        } else {
+            r
+        }
+    }
+
+    fn log10(self) -> f64 {
+        let (val, shift) = match self < f64::MIN_POSITIVE {
+            true => (self * 2f64.powi(54), 54), // subnormal.
+            false => (self, 0),
+        };
+        let bits = val.to_bits();
+        let exp2 = ((bits >> 52) & 0x7ff) as i32 - 1023 - shift;
+        let mantissa = f64::from_bits(bits & !(0x7ff << 52) | 1023 << 52); // in [1, 2).
+        (exp2 as f64 + mantissa - 1.) * core::f64::consts::LOG10_2
+    }
+}
 
 #[inline]
 pub fn rounded(val: f64, dec: usize) -> f64 {
     match dec {
         0 => val.round(),
-        1 => (val * 10.).round() / 10.,
-        2 => (val * 100.).round() / 100.,
-        _ => unreachable!(),
+        _ => {
+            let pow = 10f64.powi(dec as i32);
+            (val * pow).round() / pow
+        }
+    }
+}
+
+/// Divide `mag` by `div`, rounding half away from zero to `dec` decimals, like [`rounded`] does,
+/// and return it as an integer scaled by `10^dec`, saturating when it doesn't fit.
+#[inline]
+pub fn div_rounded(mag: u128, div: u128, dec: usize) -> u128 {
+    let (mut q, mut r) = (mag / div, mag % div);
+    for _ in 0..dec {
+        // the next digit of r * 10 / div, adding r ten times modulo div if it would overflow.
+        let (digit, rem) = match r.checked_mul(10) {
+            Some(r) => (r / div, r % div),
+            None => (0..10).fold((0, 0), |(digit, acc), _| match acc >= div - r {
+                true => (digit + 1, acc - (div - r)),
+                false => (digit, acc + r),
+            }),
+        };
+        q = q.saturating_mul(10).saturating_add(digit);
+        r = rem;
+    }
+    q.saturating_add((r >= div - r) as u128)
+}
+
+/// The canonical key of a value to compare and hash it, which is its exact integer when it has
+/// one, just like rendering uses, or the float otherwise, where all NaNs are equal.
+#[derive(Clone, Copy)]
+pub struct Key {
+    pub val: f64,
+    pub exact: Option<Exact>,
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.exact, other.exact) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => self.val == other.val || self.val.is_nan() && other.val.is_nan(),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Key {}
+
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match (self.exact, self.val) {
+            (Some(exact), _) => exact.hash(state),
+            (None, val) if val.is_nan() => f64::NAN.to_bits().hash(state),
+            (None, val) => val.to_bits().hash(state), // zeros are always exact.
+        }
     }
 }
 
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let ord = match (self.exact, other.exact) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            _ => match self.val.partial_cmp(&other.val) {
+                Some(ord) => ord,
+                None if self.val.is_nan() && other.val.is_nan() => Ordering::Equal,
+                None => return None,
+            },
+        };
+        // the same float can be an exact integer and an approximate one beyond 2^64.
+        Some(ord.then(self.exact.is_some().cmp(&other.exact.is_some())))
+    }
+}
+
+/// The decimal exponent of a value, i.e. its number of integer digits minus one.
+///
+/// It is exact within the powers of ten that `f64` represents exactly, up to `1e±22`, while
+/// beyond them it may be one off right around the powers, which rounding the mantissa corrects.
+pub fn exponent(val: f64) -> i32 {
+    let val = val.abs();
+    if val == 0. || !val.is_finite() {
+        return 0;
+    }
+    corrected_exponent(val, val.log10().floor() as i32)
+}
+
+/// Correct an approximate decimal exponent of a positive value, which may be off by one.
+fn corrected_exponent(val: f64, exp: i32) -> i32 {
+    // the powers below `1e-308` underflow, so the tiniest values are compared scaled up.
+    let (val, pow) = match exp < -300 {
+        true => (val * 1e300, exp + 300),
+        false => (val, exp),
+    };
+    if 10f64.powi(pow) > val {
+        exp - 1
+    } else if 10f64.powi(pow + 1) <= val {
+        exp + 1
+    } else {
+        exp
+    }
+}
+
+/// Round a value to the decimals of the precision, where `auto` is the default for the scale.
+pub fn fixed(val: f64, precision: Precision, auto: usize) -> Fixed {
+    let dec = match precision {
+        Precision::Significant(_) => {
+            // rounding might carry into a new digit, like 9.996 -> 10.00, which needs one less.
+            let exp = exponent(val);
+            match precision.decimals(auto, exp) {
+                dec if exponent(rounded(val, dec)) > exp => precision.decimals(auto, exp + 1),
+                dec => dec,
+            }
+        }
+        _ => precision.decimals(auto, 0),
+    };
+    let r = rounded(val, dec);
+    Fixed {
+        neg: r < 0.,
+        scaled: (r.abs() * 10f64.powi(dec as i32)).round() as u128,
+        dec,
+        trim: precision == Precision::Auto,
+    }
+}
+
+/// Round an exact magnitude divided by `div` like [`fixed`], but with integer math.
+pub fn fixed_exact(neg: bool, mag: u128, div: u128, precision: Precision, auto: usize) -> Fixed {
+    let dec = match precision {
+        Precision::Significant(_) => {
+            let exp = (mag / div).checked_ilog10().unwrap_or(0); // only zero is below one.
+            let carry = |dec: usize| div_rounded(mag, div, dec) / 10u128.pow(dec as u32);
+            match precision.decimals(auto, exp as i32) {
+                dec if 10u128.checked_pow(exp + 1).is_some_and(|p| carry(dec) >= p) => {
+                    precision.decimals(auto, exp as i32 + 1)
+                }
+                dec => dec,
+            }
+        }
+        _ => precision.decimals(auto, 0),
+    };
+    Fixed {
+        neg,
+        scaled: div_rounded(mag, div, dec),
+        dec,
+        trim: precision == Precision::Auto,
+    }
+}
+
+/// Render a NaN as the config string alone, or an infinity with its sign followed by the units.
+pub fn fmt_non_finite(
+    f: &mut dyn fmt::Write,
+    val: f64,
+    config: &HumanConfig,
+    units: fmt::Arguments<'_>,
+) -> fmt::Result {
+    if val.is_nan() {
+        return f.write_str(config.nan);
+    }
+    let sign = if val < 0. { "-" } else { "" };
+    write!(f, "{}{}{}", sign, config.infinity, units)
+}
+
+/// The width of the numbers of a precision, up to `int` integer digits, and down to the values
+/// just below one when `below_one` is set; smaller ones can take more significant decimals than
+/// any width, so they are left to overflow it.
+pub fn num_width(precision: Precision, int: usize, below_one: bool) -> usize {
+    let width = |int: usize, dec: usize| if dec > 0 { int + 1 + dec } else { int };
+    let lowest = if below_one { 0 } else { 1 };
+    (lowest..=int)
+        .map(|e| width(e.max(1), precision.decimals(0, e as i32 - 1)))
+        .max()
+        .unwrap_or(0)
+}
+
+/// An exact decimal number, as an integer scaled by `10^dec`, which renders with `dec` decimals,
+/// or without trailing zeros when `trim` is set.
+pub struct Fixed {
+    pub neg: bool,
+    pub scaled: u128,
+    pub dec: usize,
+    pub trim: bool,
+}
+
+impl Fixed {
+    /// Whether the absolute value is at least `limit`.
+    pub fn at_least(&self, limit: u128) -> bool {
+        self.scaled >= limit.saturating_mul(10u128.pow(self.dec as u32))
+    }
+
+    /// Whether it renders exactly as `1`, to choose between singular and plural names.
+    pub fn is_one(&self) -> bool {
+        self.scaled == 10u128.pow(self.dec as u32) && (self.trim || self.dec == 0)
+    }
+}
+
+impl fmt::Display for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pow = 10u128.pow(self.dec as u32);
+        let (int, mut frac, mut dec) = (self.scaled / pow, self.scaled % pow, self.dec);
+        while self.trim && dec > 0 && frac % 10 == 0 {
+            frac /= 10;
+            dec -= 1;
+        }
+        let sign = if self.neg && self.scaled != 0 {
+            "-"
+        } else {
+            ""
+        };
+        match dec {
+            0 => write!(f, "{}{}", sign, int),
+            _ => write!(f, "{}{}.{:0dec$}", sign, int, frac, dec = dec),
+        }
+    }
+}
+
+/// A [`fmt::Write`] impl that only counts the chars, and remembers the first one.
+#[derive(Default)]
+struct CharCount {
+    chars: usize,
+    first: Option<char>,
+}
+
+impl fmt::Write for CharCount {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.first = self.first.or_else(|| s.chars().next());
+        self.chars += s.chars().count();
+        Ok(())
+    }
+}
+
+/// Render with the width, fill, and alignment of the formatter like `str` does, as well as the
+/// `+` flag for non-negative numbers, without allocating: a first pass only counts the chars.
+///
+/// The `fixed` width is used when the formatter has none, right-aligned.
+pub fn pad(
+    f: &mut fmt::Formatter<'_>,
+    fixed: Option<usize>,
+    render: impl Fn(&mut dyn fmt::Write) -> fmt::Result,
+) -> fmt::Result {
+    let (width, align) = match f.width() {
+        Some(width) => (Some(width), f.align()),
+        None => (fixed, Some(fmt::Alignment::Right)),
+    };
+    if width.is_none() && !f.sign_plus() {
+        return render(f);
+    }
+
+    let mut count = CharCount::default();
+    render(&mut count)?;
+    let plus = f.sign_plus() && count.first.is_some_and(|c| c.is_ascii_digit());
+    let padding = width
+        .unwrap_or(0)
+        .saturating_sub(count.chars + plus as usize);
+    let (pre, post) = match align {
+        None | Some(fmt::Alignment::Left) => (0, padding),
+        Some(fmt::Alignment::Right) => (padding, 0),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+    };
+    let fill = f.fill();
+    (0..pre).try_for_each(|_| f.write_char(fill))?;
+    if plus {
+        f.write_char('+')?;
+    }
+    render(f)?;
+    (0..post).try_for_each(|_| f.write_char(fill))
+}
+
 pub struct DisplayCompare<'a, I>(&'a mut I);
 
 impl<I: Iterator<Item = u8>> fmt::Write for DisplayCompare<'_, I> {
@@ -37,3 +449,138 @@ pub fn display_compare(expected: &str, human: &impl fmt::Display) -> bool {
     use fmt::Write;
     write!(DisplayCompare(it.by_ref()), "{human}").map_or(false, |_| it.len() == 0)
 }
+
+/// Render a human representation into a byte slice, returning its length, or zero if it
+/// doesn't fit.
+pub fn write_to(buf: &mut [u8], human: &impl fmt::Display) -> usize {
+    let mut w = SliceWriter::new(buf);
+    match write!(w, "{}", human) {
+        Ok(()) => w.as_str().len(),
+        Err(_) => 0,
+    }
+}
+
+/// A [`fmt::Write`] impl backed by a byte slice, which fails when it gets full.
+pub struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        SliceWriter { buf, len: 0 }
+    }
+
+    pub fn as_str(&self) -> &str {
+        // only whole `str`s are ever written.
+        core::str::from_utf8(&self.buf[..self.len]).unwrap()
+    }
+}
+
+impl fmt::Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        let dst = self.buf.get_mut(self.len..end).ok_or(fmt::Error)?;
+        dst.copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[cfg(all(test, not(feature = "std")))]
+mod tests {
+    use super::*;
+    use std::format;
+
+    // the std methods are also linked in tests, and win over the trait ones unless qualified.
+
+    #[test]
+    fn floor_and_round() {
+        let values = [
+            0.,
+            -0.,
+            0.5,
+            -0.5,
+            1.5,
+            -2.5,
+            0.49999999999999994,
+            123.456,
+            -123.456,
+            4503599627370495.5,
+            -4503599627370497.,
+            f64::MAX,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+        ];
+        for v in values {
+            assert_eq!(v.floor(), <f64 as Float>::floor(v), "{}", v);
+            assert_eq!(v.round(), <f64 as Float>::round(v), "{}", v);
+        }
+        assert!(<f64 as Float>::floor(f64::NAN).is_nan());
+        assert!(<f64 as Float>::round(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn powi() {
+        for (v, n) in [
+            (10., 0),
+            (10., 22),
+            (10., -22),
+            (2., 54),
+            (10., 308),
+            (-1.5, 7),
+        ] {
+            assert_eq!(v.powi(n), <f64 as Float>::powi(v, n), "{} {}", v, n);
+        }
+    }
+
+    #[test]
+    fn log10() {
+        for v in [
+            5e-324,
+            1e-320,
+            2.2e-308,
+            f64::MIN_POSITIVE,
+            1e-10,
+            1.,
+            1e300,
+            f64::MAX,
+        ] {
+            let approx = <f64 as Float>::log10(v);
+            assert!((v.log10() - approx).abs() < 0.03, "{} {}", v, approx);
+        }
+    }
+
+    #[test]
+    fn exponent_from_log10() {
+        let approx = |v: f64| {
+            let exp = <f64 as Float>::floor(<f64 as Float>::log10(v)) as i32;
+            corrected_exponent(v, exp)
+        };
+        let around = |p: f64| {
+            [
+                p,
+                f64::from_bits(p.to_bits() - 1),
+                f64::from_bits(p.to_bits() + 1),
+            ]
+        };
+        let expected = |v: f64| {
+            let s = format!("{:e}", v);
+            s.split_once('e').unwrap().1.parse::<i32>().unwrap()
+        };
+        for e in -22..=22 {
+            let p = format!("1e{}", e).parse().unwrap();
+            for v in around(p) {
+                assert_eq!(expected(v), exponent(v), "{:e}", v);
+                assert_eq!(expected(v), approx(v), "{:e}", v);
+            }
+        }
+        // beyond them, they are at most one off, even for subnormals.
+        let pows = (-323..=308).map(|e| format!("1e{}", e).parse().unwrap());
+        let subnormals = [5e-324, 1e-323, 9.9e-322, 1e-310, 2.2e-308];
+        for v in pows.flat_map(around).chain(subnormals) {
+            assert!((expected(v) - exponent(v)).abs() <= 1, "{:e}", v);
+            assert!((expected(v) - approx(v)).abs() <= 1, "{:e}", v);
+        }
+    }
+}
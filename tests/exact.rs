@@ -0,0 +1,133 @@
+//! Property tests of the exact integer path, against a big integer reference.
+use human_repr::{HumanConfig, HumanCount, HumanDuration, Prefixes};
+use num_bigint::BigUint;
+use proptest::prelude::*;
+use std::time::Duration;
+
+const SI: &[&str] = &["", "k", "M", "G", "T", "P", "E", "Z", "Y", "R", "Q"];
+const IEC: &[&str] = &[
+    "", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei", "Zi", "Yi", "Ri", "Qi",
+];
+const DECIMALS: &[u32] = &[1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2];
+const DURATIONS: &[(u32, &str, u32)] = &[
+    (1000, "ns", 1),
+    (1000, "µs", 1),
+    (1000, "ms", 1),
+    (60, "s", 2),
+];
+
+/// Divide `mag` by `div`, rounding half away from zero to `dec` decimals, scaled by `10^dec`.
+fn rounded(mag: &BigUint, div: &BigUint, dec: u32) -> BigUint {
+    let mag = mag * BigUint::from(10u32).pow(dec) * 2u32;
+    (mag + div) / (div * 2u32)
+}
+
+/// Render a number scaled by `10^dec`, without trailing zeros.
+fn render(neg: bool, scaled: &BigUint, dec: u32) -> String {
+    let digits = format!("{:0>width$}", scaled, width = dec as usize + 1);
+    let (int, frac) = digits.split_at(digits.len() - dec as usize);
+    let frac = frac.trim_end_matches('0');
+    let sign = if neg && *scaled != BigUint::ZERO {
+        "-"
+    } else {
+        ""
+    };
+    match frac {
+        "" => format!("{sign}{int}"),
+        _ => format!("{sign}{int}.{frac}"),
+    }
+}
+
+fn count(neg: bool, mag: u128, prefixes: &[&str], divisor: u32) -> String {
+    let mag = BigUint::from(mag);
+    let mut div = BigUint::from(1u32);
+    for (scale, &dec) in prefixes.iter().zip(DECIMALS) {
+        let scaled = rounded(&mag, &div, dec);
+        if scaled < BigUint::from(divisor) * BigUint::from(10u32).pow(dec) {
+            return format!("{}{}B", render(neg, &scaled, dec), scale);
+        }
+        div *= divisor;
+    }
+
+    let mut exp = mag.to_string().len() as u32 - 1;
+    let mut m = rounded(&mag, &BigUint::from(10u32).pow(exp), 2);
+    if m == BigUint::from(1000u32) {
+        (m, exp) = (BigUint::from(100u32), exp + 1);
+    }
+    format!("{}e{}B", render(neg, &m, 2), exp)
+}
+
+/// The reference for durations below one minute.
+fn duration(nanos: u128) -> Option<String> {
+    let nanos = BigUint::from(nanos);
+    let mut div = BigUint::from(1u32);
+    for &(size, scale, dec) in DURATIONS {
+        let scaled = rounded(&nanos, &div, dec);
+        if scaled < BigUint::from(size) * BigUint::from(10u32).pow(dec) {
+            return Some(format!("{}{}", render(false, &scaled, dec), scale));
+        }
+        div *= size.min(1000);
+    }
+    None
+}
+
+fn si() -> HumanConfig {
+    HumanConfig::new().prefixes(Prefixes::Si).space(false)
+}
+
+proptest! {
+    #[test]
+    fn counts_u64(v: u64) {
+        let h = v.human_count_bytes().with_config(si());
+        prop_assert_eq!(count(false, v as u128, SI, 1000), h.to_string());
+    }
+
+    #[test]
+    fn counts_i128(v: i128) {
+        let h = v.human_count_bytes().with_config(si());
+        prop_assert_eq!(count(v < 0, v.unsigned_abs(), SI, 1000), h.to_string());
+    }
+
+    #[test]
+    fn counts_u128_iec(v: u128) {
+        let h = v.human_count_bytes().with_config(si().prefixes(Prefixes::Iec));
+        prop_assert_eq!(count(false, v, IEC, 1024), h.to_string());
+    }
+
+    #[test]
+    fn counts_ties(m in 1u64..1_000_000, shift in 0u32..11) {
+        // values like 1.005, 12.35, or 123.45, times a power of 1000.
+        let v = (m * 10 + 5) as u128 * 10u128.pow(3 * shift);
+        let h = v.human_count_bytes().with_config(si());
+        prop_assert_eq!(count(false, v, SI, 1000), h.to_string());
+    }
+
+    #[test]
+    fn durations(secs in 0u64..60, nanos in 0u32..1_000_000_000) {
+        let d = Duration::new(secs, nanos);
+        let h = d.human_duration().with_config(si());
+        if let Some(expected) = duration(d.as_nanos()) {
+            prop_assert_eq!(expected, h.to_string());
+        }
+    }
+
+    #[test]
+    fn durations_iso8601(secs: u64, nanos in 0u32..1_000_000_000) {
+        use human_repr::{DurationStyle, TimeUnit};
+        let d = Duration::new(secs, nanos);
+        let iso = si().duration_style(DurationStyle::Iso8601).max_unit(TimeUnit::Hours);
+        let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+        let frac = format!(".{:09}", nanos);
+        let frac = frac.trim_end_matches('0').trim_end_matches('.');
+        let mut expected = "PT".to_owned();
+        for (n, designator) in [(h, "H"), (m, "M")] {
+            if n > 0 {
+                expected += &format!("{n}{designator}");
+            }
+        }
+        if s > 0 || nanos > 0 || expected == "PT" {
+            expected += &format!("{s}{frac}S");
+        }
+        prop_assert_eq!(expected, d.human_duration().with_config(iso).to_string());
+    }
+}